@@ -1,4 +0,0 @@
-use anchor_lang::prelude::*;
-
-#[constant]
-pub const SOL_USD_FEED_ID:&str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
\ No newline at end of file
@@ -1,7 +1,5 @@
 mod my_const;
 mod seeds;
-mod feed_ids;
 
 pub use my_const::*;
 pub use seeds::*;
-pub use feed_ids::*;
@@ -2,4 +2,37 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const MINT_DECIMALS: u8 = 9;
-pub const MAX_AGE: u64 = 100000;
+//基点分母，10000bps = 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+//铸币和赎回手续费上限(bps)
+pub const MAX_FEE_BPS: u64 = 1_000;
+//预言机置信区间系数k上限(bps)，即k最大为5
+pub const MAX_ORACLE_CONF_MULTIPLIER_BPS: u64 = 50_000;
+//预言机价格Partial验证时要求的最少签名数
+pub const MIN_PARTIAL_VERIFICATION_SIGNATURES: u8 = 5;
+//每种抵押资产最多可配置的备用预言机数量
+pub const MAX_SECONDARY_ORACLES: usize = 4;
+//价格历史环形缓冲区保存的观测数量
+pub const PRICE_HISTORY_CAPACITY: usize = 32;
+//债务利率指数精度，指数为1.0时等于该值
+pub const DEBT_INDEX_PRECISION: u128 = 1_000_000_000_000_000_000;
+//定点数精度(WAD)，健康因子等比例值以该精度表示，1.0时等于该值
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+//百分比分母，max_ltv/liquidation_threshold/liquidation_bonus/min_health_factor均以百分比配置
+pub const PERCENT_DENOMINATOR: u64 = 100;
+//清算奖励上限(百分比)
+pub const MAX_LIQUIDATION_BONUS: u64 = 20;
+//Config.pause_flags中各操作的暂停标志位
+pub const PAUSE_DEPOSIT: u8 = 1 << 0;
+pub const PAUSE_MINT: u8 = 1 << 1;
+pub const PAUSE_REPAY: u8 = 1 << 2;
+pub const PAUSE_WITHDRAW: u8 = 1 << 3;
+pub const PAUSE_LIQUIDATE: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_DEPOSIT | PAUSE_MINT | PAUSE_REPAY | PAUSE_WITHDRAW | PAUSE_LIQUIDATE;
+//多签成员数量上限
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+//多签交易可引用的账户数量和指令数据长度上限
+pub const MAX_MULTISIG_TRANSACTION_ACCOUNTS: usize = 24;
+pub const MAX_MULTISIG_TRANSACTION_DATA: usize = 512;
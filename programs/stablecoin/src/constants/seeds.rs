@@ -5,3 +5,11 @@ pub const MINT_ACCOUNT: &[u8] = b"mint_account";
 pub const CONFIG_ACCOUNT: &[u8] = b"config_account";
 pub const DEPOSIT_ASSET_ACCOUNT: &[u8] = b"deposit_asset_account";
 pub const COLLATERAL_ACCOUNT: &[u8] = b"collateral_account";
+pub const COLLATERAL_TYPE_ACCOUNT: &[u8] = b"collateral_type_account";
+pub const TREASURY_ACCOUNT: &[u8] = b"treasury_account";
+pub const PRICE_HISTORY_ACCOUNT: &[u8] = b"price_history_account";
+pub const PRICE_OVERRIDE_ACCOUNT: &[u8] = b"price_override_account";
+pub const PENDING_CONFIG_CHANGE_ACCOUNT: &[u8] = b"pending_config_change_account";
+pub const MULTISIG_ACCOUNT: &[u8] = b"multisig_account";
+pub const MULTISIG_SIGNER_ACCOUNT: &[u8] = b"multisig_signer_account";
+pub const MULTISIG_TRANSACTION_ACCOUNT: &[u8] = b"multisig_transaction_account";
@@ -8,4 +8,92 @@ pub enum ErrorCode {
     HealthFactorGreaterThanOne,
     #[msg("Health factor greater than min health factor!")]
     HealthFactorGreaterMinHealthFactor,
+    #[msg("Invalid risk parameters!")]
+    InvalidRiskParameters,
+    #[msg("Collateral type is disabled!")]
+    CollateralTypeDisabled,
+    #[msg("Debt ceiling exceeded!")]
+    DebtCeilingExceeded,
+    #[msg("Amount to mint exceeds max LTV!")]
+    MintAmountExceedsMaxLtv,
+    #[msg("Amount to repay exceeds debt!")]
+    RepayAmountExceedsDebt,
+    #[msg("Amount to withdraw exceeds deposited collateral!")]
+    WithdrawAmountExceedsCollateral,
+    #[msg("Invalid fee parameters!")]
+    InvalidFeeParameters,
+    #[msg("Invalid treasury withdraw accounts!")]
+    InvalidTreasuryWithdrawAccounts,
+    #[msg("Invalid oracle price!")]
+    InvalidOraclePrice,
+    #[msg("Invalid oracle parameters!")]
+    InvalidOracleParameters,
+    #[msg("Oracle price confidence interval too wide!")]
+    OracleConfidenceTooWide,
+    #[msg("Invalid oracle account!")]
+    InvalidOracleAccount,
+    #[msg("Oracle price is too old!")]
+    OraclePriceTooOld,
+    #[msg("Oracle feed id mismatch!")]
+    OracleFeedIdMismatch,
+    #[msg("Oracle verification level too low!")]
+    OracleVerificationLevelTooLow,
+    #[msg("Oracle prices deviate too much!")]
+    OracleDeviationTooLarge,
+    #[msg("Too many oracle accounts!")]
+    TooManyOracleAccounts,
+    #[msg("Price history account is required!")]
+    PriceHistoryRequired,
+    #[msg("TWAP price is unavailable!")]
+    TwapUnavailable,
+    #[msg("Circuit breaker tripped!")]
+    CircuitBreakerTripped,
+    #[msg("Invalid price override!")]
+    InvalidPriceOverride,
+    #[msg("Invalid pending authority!")]
+    InvalidPendingAuthority,
+    #[msg("Signer does not hold the required role!")]
+    Unauthorized,
+    #[msg("Operation is paused!")]
+    OperationPaused,
+    #[msg("Invalid pause flags!")]
+    InvalidPauseFlags,
+    #[msg("Invalid config change!")]
+    InvalidConfigChange,
+    #[msg("Config change is not ready to execute!")]
+    ConfigChangeNotReady,
+    #[msg("Invalid multisig owners!")]
+    InvalidMultisigOwners,
+    #[msg("Invalid multisig threshold!")]
+    InvalidMultisigThreshold,
+    #[msg("Signer is not a multisig owner!")]
+    NotMultisigOwner,
+    #[msg("Multisig transaction is too large!")]
+    MultisigTransactionTooLarge,
+    #[msg("Multisig transaction already executed!")]
+    MultisigTransactionAlreadyExecuted,
+    #[msg("Multisig owners changed since the transaction was proposed!")]
+    MultisigOwnersChanged,
+    #[msg("Not enough multisig approvals!")]
+    NotEnoughApprovals,
+    #[msg("Market debt ceiling exceeded!")]
+    MarketDebtCeilingExceeded,
+    #[msg("Invalid collateral type accounts!")]
+    InvalidCollateralTypeAccounts,
+    #[msg("Invalid liquidation amount!")]
+    InvalidLiquidationAmount,
+    #[msg("Liquidation amount exceeds close factor!")]
+    LiquidationExceedsCloseFactor,
+    #[msg("Secondary oracle accounts do not match the collateral type!")]
+    InvalidSecondaryOracleAccounts,
+    #[msg("Duplicate oracle account!")]
+    DuplicateOracleAccount,
+    #[msg("Price override account is required!")]
+    PriceOverrideRequired,
+    #[msg("Collateral type does not match the pending config change!")]
+    ConfigChangeTargetMismatch,
+    #[msg("Not enough collateral to seize for the repaid debt!")]
+    InsufficientCollateralToSeize,
+    #[msg("Math overflow!")]
+    MathOverflow,
 }
@@ -0,0 +1,2 @@
+mod my_event;
+pub use my_event::*;
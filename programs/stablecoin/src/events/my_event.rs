@@ -0,0 +1,210 @@
+use crate::states::{OracleAggregation, OracleSource, PriceMode, Role};
+use anchor_lang::prelude::*;
+
+///配置项的取值，用于记录配置修改前后的值
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigValue {
+    U64(u64),
+    Bool(bool),
+    Pubkey(Pubkey),
+    OracleSource(OracleSource),
+    OracleAggregation(OracleAggregation),
+    PriceMode(PriceMode),
+}
+
+impl From<u64> for ConfigValue {
+    fn from(value: u64) -> Self {
+        ConfigValue::U64(value)
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        ConfigValue::Bool(value)
+    }
+}
+
+impl From<Pubkey> for ConfigValue {
+    fn from(value: Pubkey) -> Self {
+        ConfigValue::Pubkey(value)
+    }
+}
+
+impl From<OracleSource> for ConfigValue {
+    fn from(value: OracleSource) -> Self {
+        ConfigValue::OracleSource(value)
+    }
+}
+
+impl From<OracleAggregation> for ConfigValue {
+    fn from(value: OracleAggregation) -> Self {
+        ConfigValue::OracleAggregation(value)
+    }
+}
+
+impl From<PriceMode> for ConfigValue {
+    fn from(value: PriceMode) -> Self {
+        ConfigValue::PriceMode(value)
+    }
+}
+
+///每修改一个配置项记录一次修改前后的值
+#[event]
+pub struct ConfigUpdated {
+    pub config_account: Pubkey,
+    //修改抵押资产类型参数时为该账户，修改市场参数时为默认公钥
+    pub collateral_type: Pubkey,
+    pub admin: Pubkey,
+    pub field: String,
+    pub old_value: ConfigValue,
+    pub new_value: ConfigValue,
+    pub update_time: i64,
+}
+
+#[event]
+pub struct PriceOverrideSet {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub effective_at: i64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct PriceOverrideCleared {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub cleared_at: i64,
+}
+
+#[event]
+pub struct PriceOverrideUsed {
+    pub collateral_type: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub used_at: i64,
+}
+
+///价格偏离过大触发熔断，paused_until之前暂停铸币和清算
+#[event]
+pub struct CircuitBreakerTriggered {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub reference_price: u64,
+    pub price: u64,
+    pub paused_until: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub config_account: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+///管理员变更，new_authority为默认公钥时表示已放弃管理权限
+#[event]
+pub struct AuthorityTransferred {
+    pub config_account: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub config_account: Pubkey,
+    pub role: Role,
+    pub previous_holder: Pubkey,
+    pub grantee: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub config_account: Pubkey,
+    pub role: Role,
+    pub previous_holder: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub config_account: Pubkey,
+    pub admin: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub proposer: Pubkey,
+    pub max_ltv: Option<u64>,
+    pub liquidation_threshold: Option<u64>,
+    pub liquidation_bonus: Option<u64>,
+    pub min_health_factor: Option<u64>,
+    pub risk_param_timelock_secs: Option<u64>,
+    pub eta: i64,
+}
+
+///各配置项修改前后的值由ConfigUpdated事件记录
+#[event]
+pub struct ConfigChangeExecuted {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub admin: Pubkey,
+    pub queued_at: i64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub admin: Pubkey,
+    pub queued_at: i64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub multisig_signer: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigTransactionProposed {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub transaction_index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct MultisigTransactionApproved {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub owner: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigTransactionExecuted {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct MultisigOwnersChanged {
+    pub multisig: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub owner_set_seqno: u32,
+}
@@ -0,0 +1,53 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::AuthorityTransferred;
+use crate::states::Config;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///被提议的新管理员签名接受管理权限
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = pending_authority @ ErrorCode::InvalidPendingAuthority,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>, _timestamp: i64) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    //没有待接受的提议时pending_authority为默认值，无法签名
+    if config_account.pending_authority == Pubkey::default() {
+        return Err(ErrorCode::InvalidPendingAuthority.into());
+    }
+    let old_authority = config_account.authority;
+    //旧管理员持有的角色一并转给新管理员，已授予其他人的角色保持不变
+    let new_authority = config_account.pending_authority;
+    config_account.roles.transfer_all(&old_authority, new_authority);
+    config_account.authority = new_authority;
+    config_account.pending_authority = Pubkey::default();
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(AuthorityTransferred {
+        config_account: config_account.key(),
+        old_authority,
+        new_authority: config_account.authority,
+    });
+    msg!(
+        "authority transferred from {} to {}",
+        old_authority,
+        config_account.authority
+    );
+
+    Ok(())
+}
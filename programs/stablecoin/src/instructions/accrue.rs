@@ -0,0 +1,27 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::states::Config;
+use crate::utils::accrue_stability_fee;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///任何人都可以调用，把市场的债务利率指数累计到当前时间
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct Accrue<'info> {
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn accrue_handler(ctx: Context<Accrue>, _timestamp: i64) -> Result<()> {
+    accrue_stability_fee(&mut ctx.accounts.config_account, Clock::get()?.unix_timestamp)
+}
@@ -0,0 +1,52 @@
+use crate::constants::MULTISIG_ACCOUNT;
+use crate::errors::ErrorCode;
+use crate::events::MultisigTransactionApproved;
+use crate::states::{Multisig, MultisigTransaction};
+use anchor_lang::prelude::*;
+
+///多签成员批准交易
+#[derive(Accounts)]
+pub struct ApproveMultisigTransaction<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MULTISIG_ACCOUNT, multisig.creator.as_ref(), &multisig.multisig_id.to_le_bytes()],
+        bump = multisig.self_bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        has_one = multisig,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+}
+
+pub fn approve_multisig_transaction_handler(
+    ctx: Context<ApproveMultisigTransaction>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let transaction = &mut ctx.accounts.transaction;
+    if transaction.executed {
+        return Err(ErrorCode::MultisigTransactionAlreadyExecuted.into());
+    }
+    if transaction.owner_set_seqno != multisig.owner_set_seqno {
+        return Err(ErrorCode::MultisigOwnersChanged.into());
+    }
+    let owner_index = multisig
+        .owner_index(&ctx.accounts.owner.key())
+        .ok_or(ErrorCode::NotMultisigOwner)?;
+    transaction.signers[owner_index] = true;
+    emit!(MultisigTransactionApproved {
+        multisig: multisig.key(),
+        transaction: transaction.key(),
+        owner: ctx.accounts.owner.key(),
+        approvals: transaction.approvals() as u8,
+        threshold: multisig.threshold,
+    });
+    msg!(
+        "multisig transaction approvals:{}/{}",
+        transaction.approvals(),
+        multisig.threshold
+    );
+
+    Ok(())
+}
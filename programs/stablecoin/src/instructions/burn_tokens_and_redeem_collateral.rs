@@ -1,13 +1,18 @@
-use crate::constants::{COLLATERAL_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT, MINT_ACCOUNT};
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT,
+    MINT_ACCOUNT, PAUSE_REPAY, PAUSE_WITHDRAW, PRICE_HISTORY_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
 use crate::redeem_or_withdraw_collateral;
-use crate::states::{Collateral, Config};
+use crate::states::{Collateral, CollateralType, Config, PriceHistory, PriceOverride};
 use crate::utils::{
-    burn_tokens, calc_redeemable_amount,
-    check_health_factor_when_burn_tokens_and_redeem_collateral, get_collateral_in_usd,
+    accrue_stability_fee, burn_tokens, calc_debt_from_normalized, calc_fee,
+    calc_normalized_debt_to_repay, calc_redeemable_amount,
+    check_health_factor_when_burn_tokens_and_redeem_collateral, get_collateral_price,
+    load_price_sources, pay_fee_to_treasury, select_collateral_price,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(timestamp: i64)]
@@ -22,30 +27,79 @@ pub struct BurnTokensAndRedeemCollateral<'info> {
     pub mint_account: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
-        seeds = [COLLATERAL_ACCOUNT, depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        seeds = [COLLATERAL_ACCOUNT, collateral_mint.key().as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
         bump = collateral_account.self_bump,
+        has_one = collateral_type,
+        has_one = collateral_mint,
         has_one = deposited_asset_account,
-        has_one = receive_stablecoin_account,
     )]
     pub collateral_account: Account<'info, Collateral>,
     #[account(
+        mut,
         seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
         bump = config_account.self_bump,
         has_one = mint_account,
+        has_one = treasury_account,
     )]
     pub config_account: Account<'info, Config>,
     #[account(
         mut,
-        seeds = [DEPOSIT_ASSET_ACCOUNT, depositor.key().as_ref(), &timestamp.to_le_bytes()],
-        bump
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        has_one = collateral_mint,
     )]
-    pub deposited_asset_account: SystemAccount<'info>,
-    #[account(mut)]
-    pub receive_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: The account's data is validated manually within the handler.
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.key().as_ref()],
+        bump = collateral_account.deposited_asset_account_bump,
+    )]
+    pub deposited_asset_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = depositor,
+        token::token_program = collateral_token_program,
+    )]
+    pub depositor_collateral_account: InterfaceAccount<'info, TokenAccount>,
+    //用于销毁的稳定币账户，可以是签名人控制的任意稳定币账户
+    #[account(
+        mut,
+        token::mint = mint_account,
+        token::authority = depositor,
+        token::token_program = token_program,
+    )]
+    pub depositor_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    pub treasury_account: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
     pub price_update: UncheckedAccount<'info>,
+    //计算健康因子使用TWAP时需要传入
+    #[account(
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_history.self_bump,
+    )]
+    pub price_history: Option<Account<'info, PriceHistory>>,
+    //预言机价格不可用时使用的管理员覆盖价格
+    #[account(
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+    )]
+    pub price_override: Option<Account<'info, PriceOverride>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token2022>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
     // pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -54,64 +108,132 @@ pub fn burn_and_redeem_handler(
     timestamp: i64,
     amount_to_burn: u64,
 ) -> Result<()> {
+    //销毁稳定币同时赎回抵押物，需要还款和取出都未暂停
+    let config_account = &ctx.accounts.config_account;
+    config_account.check_not_paused(PAUSE_REPAY)?;
+    config_account.check_not_paused(PAUSE_WITHDRAW)?;
     let collateral_account = &mut ctx.accounts.collateral_account;
     let key = ctx.accounts.depositor.key();
-    let bump = collateral_account.deposited_asset_account_bump;
-    msg!("1 deposited_asset_account_bump:{}", bump);
+    let collateral_mint_key = ctx.accounts.collateral_mint.key();
+    let bump = collateral_account.self_bump;
     let signer_seeds: &[&[&[u8]]] = &[&[
-        DEPOSIT_ASSET_ACCOUNT,
+        COLLATERAL_ACCOUNT,
+        collateral_mint_key.as_ref(),
         key.as_ref(),
         &timestamp.to_le_bytes(),
         &[bump],
     ]];
-    msg!(
-        "burn and redeem deposited_asset_account signer_seeds:{:?}",
-        signer_seeds
-    );
 
-    let config_account = &ctx.accounts.config_account;
-    // let price_update = &ctx.accounts.price_update;
-    let price_update_account = &ctx.accounts.price_update.to_account_info();
-    let price_update_data = price_update_account.try_borrow_data()?;
-    let mut price_update_data = price_update_data.iter().as_slice();
-    let price_update = PriceUpdateV2::try_deserialize(&mut price_update_data)?;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let debt_index = config_account.debt_index;
+    let current_debt = calc_debt_from_normalized(collateral_account.normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let price_sources = load_price_sources(
+        &ctx.accounts.price_update.to_account_info(),
+        ctx.remaining_accounts,
+        collateral_type,
+        config_account,
+    )?;
 
-    let collateral_in_usd = get_collateral_in_usd(&price_update)?;
+    let collateral_price = get_collateral_price(
+        &price_sources,
+        ctx.accounts.price_override.as_deref(),
+        collateral_type,
+        config_account,
+    )?;
 
-    let redeemable_amount = calc_redeemable_amount(amount_to_burn, collateral_in_usd)
-        .expect("Invoke method calc_redeemable_amount encountered unexpected error!");
-    let configured_min_health_factor = ctx.accounts.config_account.min_health_factor;
+    let risk_price = select_collateral_price(
+        collateral_price,
+        ctx.accounts.price_history.as_deref(),
+        config_account.redeem_price_mode,
+        config_account,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    //赎回数量按price + k*conf换算，健康因子按price - k*conf估值
+    let redeemable_amount =
+        calc_redeemable_amount(amount_to_burn, collateral_price.upper, collateral_type.decimals)
+            .ok_or(ErrorCode::MathOverflow)?;
+    if amount_to_burn > current_debt {
+        return Err(ErrorCode::RepayAmountExceedsDebt.into());
+    }
+    if redeemable_amount > collateral_account.deposited_asset_amount {
+        return Err(ErrorCode::WithdrawAmountExceedsCollateral.into());
+    }
+    let configured_min_health_factor = config_account.min_health_factor;
     check_health_factor_when_burn_tokens_and_redeem_collateral(
-        &price_update,
-        collateral_account.deposited_asset_lamports,
-        collateral_account.stablecoin_minted_amount,
+        risk_price.lower,
+        collateral_type.decimals,
+        collateral_account.deposited_asset_amount,
+        current_debt,
         redeemable_amount,
         amount_to_burn,
-        config_account.liquidation_threshold,
+        collateral_type.liquidation_threshold,
         configured_min_health_factor,
     )?;
     msg!("Health check passed!");
+    let normalized_debt_to_repay = calc_normalized_debt_to_repay(
+        amount_to_burn,
+        collateral_account.normalized_debt,
+        debt_index,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
     burn_tokens(
         amount_to_burn,
         ctx.accounts.mint_account.to_account_info(),
-        ctx.accounts.receive_stablecoin_account.to_account_info(),
+        ctx.accounts.depositor_stablecoin_account.to_account_info(),
         ctx.accounts.depositor.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
     )?;
     msg!("burn token completed!");
+    //赎回手续费按销毁的稳定币数量计算，从同一稳定币账户转入国库
+    let redeem_fee =
+        calc_fee(amount_to_burn, config_account.redeem_fee_bps).ok_or(ErrorCode::MathOverflow)?;
+    if redeem_fee > 0 {
+        pay_fee_to_treasury(
+            redeem_fee,
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.depositor_stablecoin_account.to_account_info(),
+            ctx.accounts.treasury_stablecoin_account.to_account_info(),
+            ctx.accounts.depositor.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        msg!("redeem fee:{}", redeem_fee);
+    }
     redeem_or_withdraw_collateral(
         redeemable_amount,
+        collateral_type.decimals,
+        ctx.accounts.collateral_mint.to_account_info(),
         ctx.accounts.deposited_asset_account.to_account_info(),
-        ctx.accounts.depositor.to_account_info(),
+        ctx.accounts.depositor_collateral_account.to_account_info(),
+        collateral_account.to_account_info(),
         signer_seeds,
-        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.collateral_token_program.to_account_info(),
     )?;
     msg!("redeem collateral completed!");
-    collateral_account.deposited_asset_lamports =
-        **ctx.accounts.deposited_asset_account.lamports.borrow() - redeemable_amount;
-    collateral_account.stablecoin_minted_amount =
-        ctx.accounts.receive_stablecoin_account.amount - amount_to_burn;
+    collateral_account.deposited_asset_amount = collateral_account
+        .deposited_asset_amount
+        .checked_sub(redeemable_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.normalized_debt = collateral_account
+        .normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
     collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_normalized_debt = collateral_type
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    config_account.total_normalized_debt = config_account
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_type.total_collateral = collateral_type
+        .total_collateral
+        .checked_sub(redeemable_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
     msg!("update collateral_account completed!");
 
     Ok(())
@@ -0,0 +1,56 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT, PENDING_CONFIG_CHANGE_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::ConfigChangeCancelled;
+use crate::states::{Config, PendingConfigChange, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///风险管理员取消排队中的风险参数修改，关闭排队账户
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct CancelConfigChange<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::RiskAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            PENDING_CONFIG_CHANGE_ACCOUNT,
+            config_account.key().as_ref(),
+            pending_config_change.collateral_type.as_ref(),
+        ],
+        bump = pending_config_change.self_bump,
+        has_one = config_account,
+        has_one = proposer,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+pub fn cancel_config_change_handler(
+    ctx: Context<CancelConfigChange>,
+    _timestamp: i64,
+) -> Result<()> {
+    emit!(ConfigChangeCancelled {
+        config_account: ctx.accounts.config_account.key(),
+        collateral_type: ctx.accounts.pending_config_change.collateral_type,
+        admin: ctx.accounts.admin.key(),
+        queued_at: ctx.accounts.pending_config_change.queued_at,
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+    msg!("config change cancelled");
+
+    Ok(())
+}
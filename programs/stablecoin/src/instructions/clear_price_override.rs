@@ -0,0 +1,58 @@
+use crate::constants::{
+    COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::events::PriceOverrideCleared;
+use crate::states::{CollateralType, Config, PriceOverride, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///预言机管理员立即清除抵押资产的紧急覆盖价格
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct ClearPriceOverride<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::OracleAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mut,
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+        has_one = collateral_type,
+    )]
+    pub price_override: Account<'info, PriceOverride>,
+}
+
+pub fn clear_price_override_handler(
+    ctx: Context<ClearPriceOverride>,
+    _timestamp: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.price_override.expires_at = 0;
+    ctx.accounts.collateral_type.price_override_expires_at = 0;
+    emit!(PriceOverrideCleared {
+        config_account: ctx.accounts.config_account.key(),
+        collateral_type: ctx.accounts.collateral_type.key(),
+        cleared_at: now,
+    });
+    msg!("price override cleared");
+
+    Ok(())
+}
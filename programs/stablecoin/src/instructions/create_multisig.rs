@@ -0,0 +1,57 @@
+use crate::constants::{MULTISIG_ACCOUNT, MULTISIG_SIGNER_ACCOUNT};
+use crate::events::MultisigCreated;
+use crate::states::Multisig;
+use anchor_lang::prelude::*;
+
+///创建M-of-N多签，之后可以把签名PDA设置为市场的authority或管理角色
+#[derive(Accounts)]
+#[instruction(multisig_id: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [MULTISIG_ACCOUNT, creator.key().as_ref(), &multisig_id.to_le_bytes()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [MULTISIG_SIGNER_ACCOUNT, multisig.key().as_ref()],
+        bump,
+    )]
+    pub multisig_signer: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_multisig_handler(
+    ctx: Context<CreateMultisig>,
+    multisig_id: u64,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    *ctx.accounts.multisig = Multisig {
+        creator: ctx.accounts.creator.key(),
+        multisig_id,
+        owners,
+        threshold,
+        owner_set_seqno: 0,
+        transaction_count: 0,
+        self_bump: ctx.bumps.multisig,
+        signer_bump: ctx.bumps.multisig_signer,
+    };
+    ctx.accounts.multisig.validate_owners()?;
+    emit!(MultisigCreated {
+        multisig: ctx.accounts.multisig.key(),
+        multisig_signer: ctx.accounts.multisig_signer.key(),
+        owners: ctx.accounts.multisig.owners.clone(),
+        threshold,
+    });
+    msg!(
+        "multisig created, signer:{}",
+        ctx.accounts.multisig_signer.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT,
+    MINT_ACCOUNT, PAUSE_DEPOSIT,
+};
+use crate::errors::ErrorCode;
+use crate::states::{Collateral, CollateralType, Config};
+use crate::utils::deposit_collateral;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+///单独存入抵押资产，不铸造稳定币
+///首次存入时开仓，之后可以对同一仓位反复追加抵押
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct DepositCollateral<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        has_one = collateral_mint,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = depositor,
+        token::token_program = collateral_token_program,
+    )]
+    pub depositor_collateral_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [COLLATERAL_ACCOUNT, collateral_mint.key().as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        space = 8 + Collateral::INIT_SPACE,
+        bump,
+    )]
+    pub collateral_account: Account<'info, Collateral>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_account,
+        token::token_program = collateral_token_program,
+    )]
+    pub deposited_asset_account: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn deposit_collateral_handler(
+    ctx: Context<DepositCollateral>,
+    timestamp: i64,
+    amount_to_deposit: u64,
+) -> Result<()> {
+    ctx.accounts.config_account.check_not_paused(PAUSE_DEPOSIT)?;
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    if !collateral_account.is_initialized {
+        //抵押资产类型被关闭后不能开新仓，已有仓位仍可追加抵押
+        if !collateral_type.enabled {
+            return Err(ErrorCode::CollateralTypeDisabled.into());
+        }
+        collateral_account.is_initialized = true;
+        collateral_account.depositor = ctx.accounts.depositor.key();
+        collateral_account.collateral_type = collateral_type.key();
+        collateral_account.collateral_mint = ctx.accounts.collateral_mint.key();
+        collateral_account.deposited_asset_account = ctx.accounts.deposited_asset_account.key();
+        collateral_account.receive_stablecoin_account =
+            get_associated_token_address_with_program_id(
+                &ctx.accounts.depositor.key(),
+                &ctx.accounts.mint_account.key(),
+                &TOKEN_2022_PROGRAM_ID,
+            );
+        collateral_account.self_bump = ctx.bumps.collateral_account;
+        collateral_account.deposited_asset_account_bump = ctx.bumps.deposited_asset_account;
+        collateral_account.init_time = timestamp;
+    }
+
+    deposit_collateral(
+        amount_to_deposit,
+        collateral_type.decimals,
+        ctx.accounts.collateral_mint.to_account_info(),
+        ctx.accounts.depositor_collateral_account.to_account_info(),
+        ctx.accounts.deposited_asset_account.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        ctx.accounts.collateral_token_program.to_account_info(),
+    )?;
+    msg!("deposit collateral completed!");
+
+    collateral_account.deposited_asset_amount = collateral_account
+        .deposited_asset_amount
+        .checked_add(amount_to_deposit)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_collateral = collateral_type
+        .total_collateral
+        .checked_add(amount_to_deposit)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
-use crate::constants::{COLLATERAL_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT, MINT_ACCOUNT};
-use crate::states::{Collateral, Config};
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT,
+    MINT_ACCOUNT, PAUSE_DEPOSIT, PAUSE_MINT, PRICE_HISTORY_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::states::{Collateral, CollateralType, Config, PriceHistory, PriceOverride};
 use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(timestamp: i64)]
@@ -12,17 +15,50 @@ pub struct DepositCollateralAndMintTokens<'info> {
     #[account(mut)]
     pub depositor: Signer<'info>,
     #[account(
+        mut,
         seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
         bump = config_account.self_bump,
-        has_one = mint_account
+        has_one = mint_account,
+        has_one = treasury_account,
     )]
     pub config_account: Account<'info, Config>,
     #[account(
         mut,
-        seeds = [DEPOSIT_ASSET_ACCOUNT, depositor.key().as_ref(), &timestamp.to_le_bytes()],
-        bump
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        has_one = collateral_mint,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = depositor,
+        token::token_program = collateral_token_program,
     )]
-    pub deposited_asset_account: SystemAccount<'info>,
+    pub depositor_collateral_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [COLLATERAL_ACCOUNT, collateral_mint.key().as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        space = 8 + Collateral::INIT_SPACE,
+        bump,
+    )]
+    pub collateral_account: Account<'info, Collateral>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_account,
+        token::token_program = collateral_token_program,
+    )]
+    pub deposited_asset_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init_if_needed,
         payer = depositor,
@@ -31,14 +67,14 @@ pub struct DepositCollateralAndMintTokens<'info> {
         associated_token::token_program = token_program
     )]
     pub receive_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    pub treasury_account: SystemAccount<'info>,
     #[account(
-        init,
-        payer = depositor,
-        seeds = [COLLATERAL_ACCOUNT, depositor.key().as_ref(), &timestamp.to_le_bytes()],
-        space = 8 + Collateral::INIT_SPACE,
-        bump,
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = token_program,
     )]
-    pub collateral_account: Account<'info, Collateral>,
+    pub treasury_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
@@ -50,10 +86,23 @@ pub struct DepositCollateralAndMintTokens<'info> {
     //     owner = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ"
     // )]
     // pub price_update: Account<'info, PriceUpdateV2>,
-    /// CHECK: The account's data is validated manually within the handler.
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
     pub price_update: UncheckedAccount<'info>,
+    //计算健康因子使用TWAP时需要传入
+    #[account(
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_history.self_bump,
+    )]
+    pub price_history: Option<Account<'info, PriceHistory>>,
+    //预言机价格不可用时使用的管理员覆盖价格
+    #[account(
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+    )]
+    pub price_override: Option<Account<'info, PriceOverride>>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token2022>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -61,11 +110,28 @@ pub fn deposit_collateral_and_mint_tokens_handler(
     ctx: Context<DepositCollateralAndMintTokens>,
     timestamp: i64,
     amount_to_deposit: u64,
+    amount_to_mint: u64,
 ) -> Result<()> {
+    //只存入不铸币时不受铸币暂停影响
+    let config_account = &ctx.accounts.config_account;
+    if amount_to_deposit > 0 {
+        config_account.check_not_paused(PAUSE_DEPOSIT)?;
+    }
+    if amount_to_mint > 0 {
+        config_account.check_not_paused(PAUSE_MINT)?;
+    }
     let collateral_account = &mut ctx.accounts.collateral_account;
+    //抵押资产类型被关闭后不能开新仓和铸币，已有仓位仍可只追加抵押
+    if (!collateral_account.is_initialized || amount_to_mint > 0)
+        && !ctx.accounts.collateral_type.enabled
+    {
+        return Err(ErrorCode::CollateralTypeDisabled.into());
+    }
     if !collateral_account.is_initialized {
         collateral_account.is_initialized = true;
         collateral_account.depositor = ctx.accounts.depositor.key();
+        collateral_account.collateral_type = ctx.accounts.collateral_type.key();
+        collateral_account.collateral_mint = ctx.accounts.collateral_mint.key();
         collateral_account.deposited_asset_account = ctx.accounts.deposited_asset_account.key();
         collateral_account.receive_stablecoin_account =
             ctx.accounts.receive_stablecoin_account.key();
@@ -73,66 +139,155 @@ pub fn deposit_collateral_and_mint_tokens_handler(
         collateral_account.deposited_asset_account_bump = ctx.bumps.deposited_asset_account;
         collateral_account.init_time = timestamp;
     }
-    let key = ctx.accounts.depositor.key();
-    let bump = collateral_account.deposited_asset_account_bump;
-    msg!("0 deposited_asset_account_bump:{}",bump);
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        DEPOSIT_ASSET_ACCOUNT,
-        key.as_ref(),
-        &timestamp.to_le_bytes(),
-        &[bump],
-    ]];
-    msg!("deposit and mint deposited_asset_account signer_seeds:{:?}", signer_seeds);
-    
-    let price_update_account = &ctx.accounts.price_update.to_account_info();
-    let price_update_data = price_update_account.try_borrow_data()?;
-    let mut price_update_data = price_update_data.iter().as_slice();
-    let price_update = PriceUpdateV2::try_deserialize(&mut price_update_data)?;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let debt_index = config_account.debt_index;
+    let current_debt = calc_debt_from_normalized(collateral_account.normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?;
 
-    let collateral_in_usd = get_collateral_in_usd(&price_update)?;
-    let mintable_amount = calc_mintable_amount(
-        amount_to_deposit,
-        collateral_in_usd,
-        ctx.accounts.config_account.max_ltv,
+    let price_sources = load_price_sources(
+        &ctx.accounts.price_update.to_account_info(),
+        ctx.remaining_accounts,
+        collateral_type,
+        config_account,
+    )?;
+
+    let collateral_price = get_collateral_price(
+        &price_sources,
+        ctx.accounts.price_override.as_deref(),
+        collateral_type,
+        config_account,
+    )?;
+    //熔断中或本次价格触发熔断时拒绝铸币，只存入不铸币时不受熔断影响
+    if amount_to_mint > 0 {
+        check_circuit_breaker(
+            config_account,
+            collateral_type,
+            collateral_price.price,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    let risk_price = select_collateral_price(
+        collateral_price,
+        ctx.accounts.price_history.as_deref(),
+        config_account.mint_price_mode,
+        config_account,
+        Clock::get()?.unix_timestamp,
+    )?;
+    //按最大抵押率计算存入后可铸造的上限，用户指定的铸造数量不能超过该上限
+    let deposited_asset_amount_after = collateral_account
+        .deposited_asset_amount
+        .checked_add(amount_to_deposit)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let max_mintable_amount = calc_mintable_amount(
+        deposited_asset_amount_after,
+        risk_price.lower,
+        collateral_type.decimals,
+        collateral_type.max_ltv,
     )
-    .expect("invoke method calc_mintable_amount encounter error!");
-    let configured_min_health_factor = ctx.accounts.config_account.min_health_factor;
+    .ok_or(ErrorCode::MathOverflow)?;
+    let amount_minted_after = current_debt
+        .checked_add(amount_to_mint)
+        .ok_or(ErrorCode::MathOverflow)?;
+    msg!(
+        "max mintable amount:{}, amount minted after mint:{}",
+        max_mintable_amount,
+        amount_minted_after
+    );
+    if amount_minted_after > max_mintable_amount {
+        return Err(ErrorCode::MintAmountExceedsMaxLtv.into());
+    }
+    let normalized_debt_to_add =
+        calc_normalized_debt_to_add(amount_to_mint, debt_index).ok_or(ErrorCode::MathOverflow)?;
+    let total_normalized_debt = collateral_type
+        .total_normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if calc_debt_from_normalized(total_normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?
+        > collateral_type.debt_ceiling
+    {
+        return Err(ErrorCode::DebtCeilingExceeded.into());
+    }
+    let market_normalized_debt = config_account
+        .total_normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if calc_debt_from_normalized(market_normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?
+        > config_account.debt_ceiling
+    {
+        return Err(ErrorCode::MarketDebtCeilingExceeded.into());
+    }
+    let configured_min_health_factor = config_account.min_health_factor;
     check_health_factor_when_deposit_collateral_and_mint_new_tokens(
-        &price_update,
-        **ctx.accounts.deposited_asset_account.lamports.borrow(),
-        ctx.accounts.receive_stablecoin_account.amount,
+        risk_price.lower,
+        collateral_type.decimals,
+        collateral_account.deposited_asset_amount,
+        current_debt,
         amount_to_deposit,
-        mintable_amount,
-        ctx.accounts.config_account.liquidation_threshold,
+        amount_to_mint,
+        collateral_type.liquidation_threshold,
         configured_min_health_factor,
     )?;
 
     deposit_collateral(
         amount_to_deposit,
-        ctx.accounts.depositor.to_account_info(),
+        collateral_type.decimals,
+        ctx.accounts.collateral_mint.to_account_info(),
+        ctx.accounts.depositor_collateral_account.to_account_info(),
         ctx.accounts.deposited_asset_account.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        ctx.accounts.collateral_token_program.to_account_info(),
     )?;
 
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        MINT_ACCOUNT,
-        &timestamp.to_le_bytes(),
-        &[ctx.bumps.mint_account],
-    ]];
-    mint_stable_coins(
-        mintable_amount,
-        ctx.accounts.mint_account.to_account_info(),
-        ctx.accounts.receive_stablecoin_account.to_account_info(),
-        ctx.accounts.mint_account.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        signer_seeds,
-    )?;
+    if amount_to_mint > 0 {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_ACCOUNT,
+            &timestamp.to_le_bytes(),
+            &[ctx.bumps.mint_account],
+        ]];
+        //铸币手续费从本次铸造数量中扣除并铸造到国库，仓位债务仍按amount_to_mint计
+        let mint_fee =
+            calc_fee(amount_to_mint, config_account.mint_fee_bps).ok_or(ErrorCode::MathOverflow)?;
+        let amount_to_receive = amount_to_mint
+            .checked_sub(mint_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        mint_stable_coins(
+            amount_to_receive,
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.receive_stablecoin_account.to_account_info(),
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        if mint_fee > 0 {
+            mint_stable_coins(
+                mint_fee,
+                ctx.accounts.mint_account.to_account_info(),
+                ctx.accounts.treasury_stablecoin_account.to_account_info(),
+                ctx.accounts.mint_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                signer_seeds,
+            )?;
+            msg!("mint fee:{}", mint_fee);
+        }
+    }
 
-    collateral_account.stablecoin_minted_amount =
-        mintable_amount + ctx.accounts.receive_stablecoin_account.amount;
-    collateral_account.deposited_asset_lamports =
-        amount_to_deposit + **ctx.accounts.deposited_asset_account.lamports.borrow();
+    collateral_account.normalized_debt = collateral_account
+        .normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.deposited_asset_amount = deposited_asset_amount_after;
     collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_normalized_debt = total_normalized_debt;
+    config_account.total_normalized_debt = market_normalized_debt;
+    collateral_type.total_collateral = collateral_type
+        .total_collateral
+        .checked_add(amount_to_deposit)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     Ok(())
 }
@@ -0,0 +1,176 @@
+use crate::constants::{
+    COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PENDING_CONFIG_CHANGE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::events::ConfigChangeExecuted;
+use crate::states::{CollateralType, Config, PendingConfigChange, Role};
+use crate::utils::{set_config_value, ConfigAudit};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///风险管理员在eta之后执行排队中的风险参数修改，执行后关闭排队账户
+///修改抵押资产类型参数时需要传入对应的collateral_type
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct ExecuteConfigChange<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::RiskAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            PENDING_CONFIG_CHANGE_ACCOUNT,
+            config_account.key().as_ref(),
+            pending_config_change.collateral_type.as_ref(),
+        ],
+        bump = pending_config_change.self_bump,
+        has_one = config_account,
+        has_one = proposer,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        constraint = collateral_type.key() == pending_config_change.collateral_type @ ErrorCode::ConfigChangeTargetMismatch,
+    )]
+    pub collateral_type: Option<Account<'info, CollateralType>>,
+}
+
+pub fn execute_config_change_handler(
+    ctx: Context<ExecuteConfigChange>,
+    _timestamp: i64,
+) -> Result<()> {
+    let pending = &ctx.accounts.pending_config_change;
+    let config_account = &mut ctx.accounts.config_account;
+    let now = Clock::get()?.unix_timestamp;
+    if now < pending.eta {
+        return Err(ErrorCode::ConfigChangeNotReady.into());
+    }
+    let audit = ConfigAudit {
+        config_account: config_account.key(),
+        collateral_type: pending.collateral_type,
+        admin: ctx.accounts.admin.key(),
+        roles: config_account.roles,
+        update_time: now,
+    };
+
+    if pending.collateral_type != Pubkey::default() {
+        let collateral_type = ctx
+            .accounts
+            .collateral_type
+            .as_mut()
+            .ok_or(ErrorCode::ConfigChangeTargetMismatch)?;
+
+        if let Some(max_ltv) = pending.max_ltv {
+            set_config_value(
+                &mut collateral_type.max_ltv,
+                max_ltv,
+                "max_ltv",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(threshold) = pending.liquidation_threshold {
+            set_config_value(
+                &mut collateral_type.liquidation_threshold,
+                threshold,
+                "liquidation_threshold",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(bonus) = pending.liquidation_bonus {
+            set_config_value(
+                &mut collateral_type.liquidation_bonus,
+                bonus,
+                "liquidation_bonus",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        collateral_type.validate_risk_parameters()?;
+        collateral_type.last_update_time = now;
+    } else {
+        if let Some(max_ltv) = pending.max_ltv {
+            set_config_value(
+                &mut config_account.max_ltv,
+                max_ltv,
+                "max_ltv",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(threshold) = pending.liquidation_threshold {
+            set_config_value(
+                &mut config_account.liquidation_threshold,
+                threshold,
+                "liquidation_threshold",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(bonus) = pending.liquidation_bonus {
+            set_config_value(
+                &mut config_account.liquidation_bonus,
+                bonus,
+                "liquidation_bonus",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(factor) = pending.min_health_factor {
+            set_config_value(
+                &mut config_account.min_health_factor,
+                factor,
+                "min_health_factor",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        if let Some(timelock) = pending.risk_param_timelock_secs {
+            set_config_value(
+                &mut config_account.risk_param_timelock_secs,
+                timelock,
+                "risk_param_timelock_secs",
+                Role::RiskAdmin,
+                &audit,
+            )?;
+        }
+
+        config_account.validate_risk_parameters()?;
+        config_account.last_update_time = now;
+    }
+    emit!(ConfigChangeExecuted {
+        config_account: config_account.key(),
+        collateral_type: pending.collateral_type,
+        admin: ctx.accounts.admin.key(),
+        queued_at: pending.queued_at,
+        executed_at: now,
+    });
+    msg!("config change executed");
+
+    Ok(())
+}
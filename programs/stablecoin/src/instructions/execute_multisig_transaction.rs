@@ -0,0 +1,61 @@
+use crate::constants::{MULTISIG_ACCOUNT, MULTISIG_SIGNER_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::MultisigTransactionExecuted;
+use crate::states::{Multisig, MultisigTransaction};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+///批准数达到阈值后执行多签交易，任何人都可以触发
+///交易引用的账户(包括被调用的程序)需要按顺序通过remaining_accounts传入
+#[derive(Accounts)]
+pub struct ExecuteMultisigTransaction<'info> {
+    #[account(
+        seeds = [MULTISIG_ACCOUNT, multisig.creator.as_ref(), &multisig.multisig_id.to_le_bytes()],
+        bump = multisig.self_bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [MULTISIG_SIGNER_ACCOUNT, multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = multisig,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+}
+
+pub fn execute_multisig_transaction_handler(
+    ctx: Context<ExecuteMultisigTransaction>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let transaction = &mut ctx.accounts.transaction;
+    if transaction.executed {
+        return Err(ErrorCode::MultisigTransactionAlreadyExecuted.into());
+    }
+    if transaction.owner_set_seqno != multisig.owner_set_seqno {
+        return Err(ErrorCode::MultisigOwnersChanged.into());
+    }
+    if transaction.approvals() < multisig.threshold as usize {
+        return Err(ErrorCode::NotEnoughApprovals.into());
+    }
+
+    let multisig_key = multisig.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MULTISIG_SIGNER_ACCOUNT,
+        multisig_key.as_ref(),
+        &[multisig.signer_bump],
+    ]];
+    let instruction = transaction.to_instruction(&ctx.accounts.multisig_signer.key());
+    invoke_signed(&instruction, ctx.remaining_accounts, signer_seeds)?;
+    transaction.executed = true;
+    emit!(MultisigTransactionExecuted {
+        multisig: multisig_key,
+        transaction: transaction.key(),
+        program_id: transaction.program_id,
+    });
+    msg!("multisig transaction executed");
+
+    Ok(())
+}
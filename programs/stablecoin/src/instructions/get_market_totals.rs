@@ -0,0 +1,127 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT, WAD};
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config};
+use crate::utils::{
+    accrue_stability_fee, calc_collateral_value, calc_debt_from_normalized,
+    get_oracle_collateral_price, load_price_sources, mul_div, Rounding,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///市场整体的债务和抵押情况，供看板和系统抵押率检查使用
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MarketTotals {
+    //累计稳定费后的市场总债务
+    pub total_debt: u64,
+    pub debt_ceiling: u64,
+    //按预言机即时价格计算的全部抵押资产价值，与稳定币同精度
+    pub total_collateral_value: u64,
+    //系统抵押率(WAD精度)，总债务为0时为u128::MAX
+    pub collateral_ratio_wad: u128,
+}
+
+///任何人都可以调用，通过返回值查询市场总债务、总抵押价值和系统抵押率
+///remaining_accounts按[抵押资产类型账户, 价格账户, 该抵押资产类型的备用预言机账户...]依次传入，需要覆盖市场的全部抵押资产类型
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct GetMarketTotals<'info> {
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn get_market_totals_handler(
+    ctx: Context<GetMarketTotals>,
+    _timestamp: i64,
+) -> Result<MarketTotals> {
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let total_debt = calc_debt_from_normalized(
+        config_account.total_normalized_debt,
+        config_account.debt_index,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut seen_collateral_types: Vec<Pubkey> =
+        Vec::with_capacity(config_account.collateral_type_count as usize);
+    let mut total_collateral_value: u64 = 0;
+    while !remaining_accounts.is_empty() {
+        let [collateral_type_info, price_update, rest @ ..] = remaining_accounts else {
+            return Err(ErrorCode::InvalidCollateralTypeAccounts.into());
+        };
+        if collateral_type_info.owner != &crate::ID
+            || seen_collateral_types.contains(collateral_type_info.key)
+        {
+            return Err(ErrorCode::InvalidCollateralTypeAccounts.into());
+        }
+        let collateral_type =
+            CollateralType::try_deserialize(&mut &collateral_type_info.try_borrow_data()?[..])?;
+        if collateral_type.config_account != config_account.key() {
+            return Err(ErrorCode::InvalidCollateralTypeAccounts.into());
+        }
+        seen_collateral_types.push(collateral_type_info.key());
+
+        let secondary_count = collateral_type.secondary_oracles.len();
+        if rest.len() < secondary_count {
+            return Err(ErrorCode::InvalidSecondaryOracleAccounts.into());
+        }
+        let (secondary_price_accounts, rest) = rest.split_at(secondary_count);
+        remaining_accounts = rest;
+
+        let price_sources = load_price_sources(
+            price_update,
+            secondary_price_accounts,
+            &collateral_type,
+            config_account,
+        )?;
+        let collateral_price =
+            get_oracle_collateral_price(&price_sources, &collateral_type, config_account)?;
+        let collateral_value = calc_collateral_value(
+            collateral_price.price,
+            collateral_type.decimals,
+            collateral_type.total_collateral,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+        total_collateral_value = total_collateral_value
+            .checked_add(collateral_value)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    if seen_collateral_types.len() != config_account.collateral_type_count as usize {
+        return Err(ErrorCode::InvalidCollateralTypeAccounts.into());
+    }
+
+    let collateral_ratio_wad = if total_debt == 0 {
+        u128::MAX
+    } else {
+        mul_div(
+            total_collateral_value as u128,
+            WAD,
+            total_debt as u128,
+            Rounding::Down,
+        )
+        .ok_or(ErrorCode::MathOverflow)?
+    };
+    msg!(
+        "total debt:{}, total collateral value:{}, collateral ratio(wad):{}",
+        total_debt,
+        total_collateral_value,
+        collateral_ratio_wad
+    );
+
+    Ok(MarketTotals {
+        total_debt,
+        debt_ceiling: config_account.debt_ceiling,
+        total_collateral_value,
+        collateral_ratio_wad,
+    })
+}
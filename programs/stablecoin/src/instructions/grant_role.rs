@@ -0,0 +1,51 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::RoleGranted;
+use crate::states::{Config, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///authority把管理角色授予给指定账户，同一角色只有一个持有人，授予时替换原持有人
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct GrantRole<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = authority,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn grant_role_handler(
+    ctx: Context<GrantRole>,
+    _timestamp: i64,
+    role: Role,
+    grantee: Pubkey,
+) -> Result<()> {
+    if grantee == Pubkey::default() {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    let config_account = &mut ctx.accounts.config_account;
+    let holder = config_account.roles.holder_mut(role);
+    let previous_holder = *holder;
+    *holder = grantee;
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(RoleGranted {
+        config_account: config_account.key(),
+        role,
+        previous_holder,
+        grantee,
+    });
+    msg!("grant role {:?} to {}", role, grantee);
+
+    Ok(())
+}
@@ -1,7 +1,44 @@
-use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT, MINT_DECIMALS};
-use crate::states::Config;
+use crate::constants::{
+    CONFIG_ACCOUNT, DEBT_INDEX_PRECISION, MINT_ACCOUNT, MINT_DECIMALS, TREASURY_ACCOUNT,
+};
+use crate::states::{Config, OracleAggregation, OracleSource, PriceMode, Roles};
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, Token2022};
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitConfigParams {
+    pub max_ltv: u64,
+    pub liquidation_threshold: u64,
+    pub liquidation_bonus: u64,
+    pub min_health_factor: u64,
+    pub risk_param_timelock_secs: u64,
+    pub stability_fee_bps: u64,
+    pub debt_ceiling: u64,
+    pub mint_fee_bps: u64,
+    pub redeem_fee_bps: u64,
+    pub liquidation_protocol_share_bps: u64,
+    pub close_factor_bps: u64,
+    pub oracle_conf_multiplier_bps: u64,
+    pub max_oracle_conf_bps: u64,
+    pub require_full_verification: bool,
+    pub oracle_source: OracleSource,
+    pub oracle_receiver_program: Pubkey,
+    pub price_signer: Pubkey,
+    pub oracle_aggregation: OracleAggregation,
+    pub max_oracle_deviation_bps: u64,
+    pub twap_window_secs: u64,
+    pub mint_price_mode: PriceMode,
+    pub redeem_price_mode: PriceMode,
+    pub liquidation_price_mode: PriceMode,
+    pub circuit_breaker_bps: u64,
+    pub circuit_breaker_window_secs: u64,
+    pub circuit_breaker_cooldown_secs: u64,
+    pub price_override_timelock_secs: u64,
+    pub max_price_override_duration_secs: u64,
+}
+
 #[derive(Accounts)]
 #[instruction(timestamp:i64)]
 pub struct InitConfig<'info> {
@@ -26,30 +63,95 @@ pub struct InitConfig<'info> {
         bump,
     )]
     pub config_account: Account<'info, Config>,
+    //国库PDA，作为SOL金库直接持有lamports
+    #[account(
+        mut,
+        seeds = [TREASURY_ACCOUNT, config_account.key().as_ref()],
+        bump,
+    )]
+    pub treasury_account: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint_account,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 pub fn init_config_handler(
     ctx: Context<InitConfig>,
     timestamp: i64,
-    max_ltv: u64,
-    liquidation_threshold: u64,
-    liquidation_bonus: u64,
-    min_health_factor: u64,
+    params: InitConfigParams,
 ) -> Result<()> {
     msg!("timestamp:{}", timestamp);
+    let now = Clock::get()?.unix_timestamp;
     *ctx.accounts.config_account = Config {
         authority: ctx.accounts.authority.key(),
+        pending_authority: Pubkey::default(),
+        roles: Roles::new(ctx.accounts.authority.key()),
         mint_account: ctx.accounts.mint_account.key(),
-        max_ltv,
-        liquidation_threshold,
-        liquidation_bonus,
-        min_health_factor,
+        max_ltv: params.max_ltv,
+        liquidation_threshold: params.liquidation_threshold,
+        liquidation_bonus: params.liquidation_bonus,
+        min_health_factor: params.min_health_factor,
+        risk_param_timelock_secs: params.risk_param_timelock_secs,
+        stability_fee_bps: params.stability_fee_bps,
+        debt_index: DEBT_INDEX_PRECISION,
+        last_accrual_time: now,
+        total_normalized_debt: 0,
+        debt_ceiling: params.debt_ceiling,
+        collateral_type_count: 0,
+        treasury_account: ctx.accounts.treasury_account.key(),
+        mint_fee_bps: params.mint_fee_bps,
+        redeem_fee_bps: params.redeem_fee_bps,
+        liquidation_protocol_share_bps: params.liquidation_protocol_share_bps,
+        close_factor_bps: params.close_factor_bps,
+        oracle_conf_multiplier_bps: params.oracle_conf_multiplier_bps,
+        max_oracle_conf_bps: params.max_oracle_conf_bps,
+        require_full_verification: params.require_full_verification,
+        oracle_source: params.oracle_source,
+        oracle_receiver_program: params.oracle_receiver_program,
+        price_signer: params.price_signer,
+        oracle_aggregation: params.oracle_aggregation,
+        max_oracle_deviation_bps: params.max_oracle_deviation_bps,
+        twap_window_secs: params.twap_window_secs,
+        mint_price_mode: params.mint_price_mode,
+        redeem_price_mode: params.redeem_price_mode,
+        liquidation_price_mode: params.liquidation_price_mode,
+        circuit_breaker_bps: params.circuit_breaker_bps,
+        circuit_breaker_window_secs: params.circuit_breaker_window_secs,
+        circuit_breaker_cooldown_secs: params.circuit_breaker_cooldown_secs,
+        circuit_breaker_paused_until: 0,
+        pause_flags: 0,
+        price_override_timelock_secs: params.price_override_timelock_secs,
+        max_price_override_duration_secs: params.max_price_override_duration_secs,
         self_bump: ctx.bumps.config_account,
         mint_account_bump: ctx.bumps.mint_account,
+        treasury_bump: ctx.bumps.treasury_account,
         init_time: timestamp,
         last_update_time: timestamp,
     };
+    ctx.accounts.config_account.validate_risk_parameters()?;
+    ctx.accounts.config_account.validate_fee_parameters()?;
+    ctx.accounts.config_account.validate_oracle_parameters()?;
+
+    //给国库PDA转入免租金额，使SOL金库账户存在于链上
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(0);
+    let treasury_lamports = ctx.accounts.treasury_account.lamports();
+    if treasury_lamports < rent_exempt_lamports {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.treasury_account.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, rent_exempt_lamports - treasury_lamports)?;
+    }
     Ok(())
 }
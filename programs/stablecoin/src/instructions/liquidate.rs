@@ -1,23 +1,43 @@
-use crate::constants::{CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT, MINT_ACCOUNT};
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT,
+    MINT_ACCOUNT, PAUSE_LIQUIDATE, PRICE_HISTORY_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
 use crate::errors::ErrorCode;
-use crate::states::{Collateral, Config};
-use crate::utils::{burn_tokens, calc_health_factor_when_liquidate};
+use crate::states::{Collateral, CollateralType, Config, PriceHistory, PriceOverride};
+use crate::utils::{
+    accrue_stability_fee, burn_tokens, calc_debt_from_normalized, calc_fee,
+    calc_health_factor_when_liquidate, calc_liquidation_bonus, calc_max_liquidation_repay,
+    calc_normalized_debt_to_repay, check_circuit_breaker, get_collateral_price, load_price_sources,
+    percent_to_wad, select_collateral_price,
+};
 use crate::{calc_liquidatable_collateral, redeem_or_withdraw_collateral};
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
 
-///清算流程：清算人burn自己账户上的稳定币，获得被抵押资产sol和一定比例的清算奖励
+///清算流程：清算人burn自己账户上的稳定币，获得被抵押资产和一定比例的清算奖励
 ///先检查健康因子判断是否可执行清算
-///参数为amount_to_burn，通过pyth计算等值的被抵押资产sol，
+///参数为amount_to_burn，通过pyth计算等值的被抵押资产，
 #[derive(Accounts)]
 #[instruction(timestamp:i64)]
 pub struct Liquidation<'info> {
     #[account(mut)]
     pub liquidator: Signer<'info>,
     // pub price_update: Account<'info, PriceUpdateV2>,
-    /// CHECK: The account's data is validated manually within the handler.
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
     pub price_update: UncheckedAccount<'info>,
+    //计算健康因子使用TWAP时需要传入
+    #[account(
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_history.self_bump,
+    )]
+    pub price_history: Option<Account<'info, PriceHistory>>,
+    //预言机价格不可用时使用的管理员覆盖价格
+    #[account(
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+    )]
+    pub price_override: Option<Account<'info, PriceOverride>>,
     #[account(
         mut,
         seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
@@ -25,22 +45,40 @@ pub struct Liquidation<'info> {
     )]
     pub mint_account: InterfaceAccount<'info, Mint>,
     #[account(
+        mut,
         seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
         bump,
         has_one = mint_account,
+        has_one = treasury_account,
     )]
     pub config_account: Account<'info, Config>,
     #[account(
         mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        has_one = collateral_mint,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_ACCOUNT, collateral_mint.key().as_ref(), collateral_account.depositor.as_ref(), &timestamp.to_le_bytes()],
+        bump = collateral_account.self_bump,
+        has_one = collateral_type,
+        has_one = collateral_mint,
         has_one = deposited_asset_account
     )]
     pub collateral_account: Account<'info, Collateral>,
     #[account(
         mut,
-        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.depositor.as_ref(), &timestamp.to_le_bytes()],
-        bump
+        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.key().as_ref()],
+        bump = collateral_account.deposited_asset_account_bump,
     )]
-    pub deposited_asset_account: SystemAccount<'info>,
+    pub deposited_asset_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         associated_token::mint = mint_account,
@@ -48,9 +86,30 @@ pub struct Liquidation<'info> {
         associated_token::token_program = token_program,
     )]
     pub receive_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    //清算人接收抵押资产的账户
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = liquidator,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub liquidator_collateral_account: InterfaceAccount<'info, TokenAccount>,
+    pub treasury_account: SystemAccount<'info>,
+    //国库接收清算奖励分成的抵押资产账户
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub treasury_collateral_account: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token2022>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 pub fn liquidator_handler(
@@ -58,24 +117,110 @@ pub fn liquidator_handler(
     timestamp:i64,
     amount_to_burn: u64,
 ) -> Result<()> {
+    ctx.accounts.config_account.check_not_paused(PAUSE_LIQUIDATE)?;
     let collateral_account = &mut ctx.accounts.collateral_account;
-    let price_update_account = &ctx.accounts.price_update.to_account_info();
-    let price_update_data = price_update_account.try_borrow_data()?;
-    let mut price_update_data = price_update_data.iter().as_slice();
-    let price_update = PriceUpdateV2::try_deserialize(&mut price_update_data)?;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let debt_index = config_account.debt_index;
+    let current_debt = calc_debt_from_normalized(collateral_account.normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let price_sources = load_price_sources(
+        &ctx.accounts.price_update.to_account_info(),
+        ctx.remaining_accounts,
+        collateral_type,
+        config_account,
+    )?;
+    let collateral_price = get_collateral_price(
+        &price_sources,
+        ctx.accounts.price_override.as_deref(),
+        collateral_type,
+        config_account,
+    )?;
+    //熔断中或本次价格触发熔断时拒绝铸币和清算
+    check_circuit_breaker(
+        config_account,
+        collateral_type,
+        collateral_price.price,
+        Clock::get()?.unix_timestamp,
+    )?;
+    let risk_price = select_collateral_price(
+        collateral_price,
+        ctx.accounts.price_history.as_deref(),
+        config_account.liquidation_price_mode,
+        config_account,
+        Clock::get()?.unix_timestamp,
+    )?;
+    //是否可清算按预言机报价判断，不因置信区间扩大而提前清算
     let health_factor = calc_health_factor_when_liquidate(
-        &price_update,
-        collateral_account.deposited_asset_lamports,
-        collateral_account.stablecoin_minted_amount,
-        ctx.accounts.config_account.liquidation_threshold,
+        risk_price.price,
+        collateral_type.decimals,
+        collateral_account.deposited_asset_amount,
+        current_debt,
+        collateral_type.liquidation_threshold,
     )
-    .expect("Invoke method calc_health_factor_when_liquidate encountered error!");
-    let configured_min_health_factor = ctx.accounts.config_account.min_health_factor;
+    .ok_or(ErrorCode::MathOverflow)?;
+    let configured_min_health_factor =
+        percent_to_wad(config_account.min_health_factor).ok_or(ErrorCode::MathOverflow)?;
     msg!("health factor when liquidate:{}", health_factor);
-    if health_factor >= configured_min_health_factor as f64 / 100.0 {
+    if health_factor >= configured_min_health_factor {
         return Err(ErrorCode::HealthFactorGreaterMinHealthFactor.into());
     }
     msg!("health checked completed!");
+    //偿还数量不超过仓位债务，且不超过清算系数允许的比例
+    if amount_to_burn == 0 {
+        return Err(ErrorCode::InvalidLiquidationAmount.into());
+    }
+    let amount_to_burn = amount_to_burn.min(current_debt);
+    let max_repay_amount =
+        calc_max_liquidation_repay(current_debt, config_account.close_factor_bps)
+            .ok_or(ErrorCode::MathOverflow)?;
+    msg!(
+        "liquidation repay amount:{}, max repay amount:{}",
+        amount_to_burn,
+        max_repay_amount
+    );
+    if amount_to_burn > max_repay_amount {
+        return Err(ErrorCode::LiquidationExceedsCloseFactor.into());
+    }
+    let normalized_debt_to_repay = calc_normalized_debt_to_repay(
+        amount_to_burn,
+        collateral_account.normalized_debt,
+        debt_index,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    //按清算价格模式下的price + k*conf把偿还的债务换算成抵押资产，避免价格不确定时多付抵押资产
+    let liquidatable_amount = calc_liquidatable_collateral(
+        risk_price.upper,
+        collateral_type.decimals,
+        amount_to_burn,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    let bonus_amount =
+        calc_liquidation_bonus(liquidatable_amount, collateral_type.liquidation_bonus)
+            .ok_or(ErrorCode::MathOverflow)?;
+    //扣押的抵押资产不超过仓位记录的数量和金库实际持有的数量，不足时先减少清算奖励
+    let seizable_amount = collateral_account
+        .deposited_asset_amount
+        .min(ctx.accounts.deposited_asset_account.amount);
+    //抵押资产不足以覆盖偿还的债务时拒绝，清算人可以减少偿还数量
+    if liquidatable_amount > seizable_amount {
+        return Err(ErrorCode::InsufficientCollateralToSeize.into());
+    }
+    let total_seized = liquidatable_amount
+        .checked_add(bonus_amount)
+        .ok_or(ErrorCode::MathOverflow)?
+        .min(seizable_amount);
+    let bonus_amount = total_seized.saturating_sub(liquidatable_amount);
+    //清算奖励中按比例分给协议国库，其余归清算人
+    let protocol_share = calc_fee(bonus_amount, config_account.liquidation_protocol_share_bps)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let liquidatable_amount = total_seized;
+    msg!(
+        "seized collateral:{}, liquidation bonus:{}",
+        liquidatable_amount,
+        bonus_amount
+    );
     burn_tokens(
         amount_to_burn,
         ctx.accounts.mint_account.to_account_info(),
@@ -84,27 +229,62 @@ pub fn liquidator_handler(
         ctx.accounts.token_program.to_account_info(),
     )?;
     msg!("burn_tokens completed!");
-    let liquidate_bonus = ctx.accounts.config_account.liquidation_bonus as f64 / 100.0;
-    let liquidatable_amount = calc_liquidatable_collateral(&price_update, amount_to_burn)
-        .expect("Invoke method calc_liquidatable_collateral encountered error!");
-    let liquidatable_amount = (1.0 + liquidate_bonus) * liquidatable_amount as f64;
+    let collateral_mint_key = ctx.accounts.collateral_mint.key();
     let signer_seeds: &[&[&[u8]]] = &[&[
-        DEPOSIT_ASSET_ACCOUNT,
+        COLLATERAL_ACCOUNT,
+        collateral_mint_key.as_ref(),
         collateral_account.depositor.as_ref(),
         &timestamp.to_le_bytes(),
-        &[collateral_account.deposited_asset_account_bump],
+        &[collateral_account.self_bump],
     ]];
+    let liquidator_amount = liquidatable_amount
+        .checked_sub(protocol_share)
+        .ok_or(ErrorCode::MathOverflow)?;
     redeem_or_withdraw_collateral(
-        liquidatable_amount as u64,
+        liquidator_amount,
+        collateral_type.decimals,
+        ctx.accounts.collateral_mint.to_account_info(),
         ctx.accounts.deposited_asset_account.to_account_info(),
-        ctx.accounts.liquidator.to_account_info(),
+        ctx.accounts.liquidator_collateral_account.to_account_info(),
+        collateral_account.to_account_info(),
         signer_seeds,
-        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.collateral_token_program.to_account_info(),
     )?;
+    if protocol_share > 0 {
+        redeem_or_withdraw_collateral(
+            protocol_share,
+            collateral_type.decimals,
+            ctx.accounts.collateral_mint.to_account_info(),
+            ctx.accounts.deposited_asset_account.to_account_info(),
+            ctx.accounts.treasury_collateral_account.to_account_info(),
+            collateral_account.to_account_info(),
+            signer_seeds,
+            ctx.accounts.collateral_token_program.to_account_info(),
+        )?;
+        msg!("liquidation protocol share:{}", protocol_share);
+    }
     msg!("withdraw collateral completed!");
-    collateral_account.deposited_asset_lamports -= liquidatable_amount as u64;
-    collateral_account.stablecoin_minted_amount -= amount_to_burn;
+    collateral_account.deposited_asset_amount = collateral_account
+        .deposited_asset_amount
+        .checked_sub(liquidatable_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.normalized_debt = collateral_account
+        .normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
     collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_normalized_debt = collateral_type
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    config_account.total_normalized_debt = config_account
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_type.total_collateral = collateral_type
+        .total_collateral
+        .checked_sub(liquidatable_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
     msg!("update collateral account completed!");
 
     Ok(())
@@ -0,0 +1,220 @@
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PAUSE_MINT,
+    PRICE_HISTORY_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::states::{Collateral, CollateralType, Config, PriceHistory, PriceOverride};
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+///以仓位中已有的抵押资产为担保铸造稳定币
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct MintAgainstCollateral<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump,
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = treasury_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_account.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_ACCOUNT, collateral_account.collateral_mint.as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = collateral_account.self_bump,
+        has_one = depositor,
+        has_one = collateral_type,
+        has_one = receive_stablecoin_account,
+    )]
+    pub collateral_account: Account<'info, Collateral>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = mint_account,
+        associated_token::authority = depositor,
+        associated_token::token_program = token_program
+    )]
+    pub receive_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    pub treasury_account: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
+    pub price_update: UncheckedAccount<'info>,
+    //计算健康因子使用TWAP时需要传入
+    #[account(
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_history.self_bump,
+    )]
+    pub price_history: Option<Account<'info, PriceHistory>>,
+    //预言机价格不可用时使用的管理员覆盖价格
+    #[account(
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+    )]
+    pub price_override: Option<Account<'info, PriceOverride>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn mint_against_collateral_handler(
+    ctx: Context<MintAgainstCollateral>,
+    timestamp: i64,
+    amount_to_mint: u64,
+) -> Result<()> {
+    ctx.accounts.config_account.check_not_paused(PAUSE_MINT)?;
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    if !collateral_type.enabled {
+        return Err(ErrorCode::CollateralTypeDisabled.into());
+    }
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let debt_index = config_account.debt_index;
+    let current_debt = calc_debt_from_normalized(collateral_account.normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let price_sources = load_price_sources(
+        &ctx.accounts.price_update.to_account_info(),
+        ctx.remaining_accounts,
+        collateral_type,
+        config_account,
+    )?;
+
+    let collateral_price = get_collateral_price(
+        &price_sources,
+        ctx.accounts.price_override.as_deref(),
+        collateral_type,
+        config_account,
+    )?;
+    //熔断中或本次价格触发熔断时拒绝铸币和清算
+    check_circuit_breaker(
+        config_account,
+        collateral_type,
+        collateral_price.price,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let risk_price = select_collateral_price(
+        collateral_price,
+        ctx.accounts.price_history.as_deref(),
+        config_account.mint_price_mode,
+        config_account,
+        Clock::get()?.unix_timestamp,
+    )?;
+    let max_mintable_amount = calc_mintable_amount(
+        collateral_account.deposited_asset_amount,
+        risk_price.lower,
+        collateral_type.decimals,
+        collateral_type.max_ltv,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    let amount_minted_after = current_debt
+        .checked_add(amount_to_mint)
+        .ok_or(ErrorCode::MathOverflow)?;
+    msg!(
+        "max mintable amount:{}, amount minted after mint:{}",
+        max_mintable_amount,
+        amount_minted_after
+    );
+    if amount_minted_after > max_mintable_amount {
+        return Err(ErrorCode::MintAmountExceedsMaxLtv.into());
+    }
+    let normalized_debt_to_add =
+        calc_normalized_debt_to_add(amount_to_mint, debt_index).ok_or(ErrorCode::MathOverflow)?;
+    let total_normalized_debt = collateral_type
+        .total_normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if calc_debt_from_normalized(total_normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?
+        > collateral_type.debt_ceiling
+    {
+        return Err(ErrorCode::DebtCeilingExceeded.into());
+    }
+    let market_normalized_debt = config_account
+        .total_normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if calc_debt_from_normalized(market_normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?
+        > config_account.debt_ceiling
+    {
+        return Err(ErrorCode::MarketDebtCeilingExceeded.into());
+    }
+    check_health_factor_when_deposit_collateral_and_mint_new_tokens(
+        risk_price.lower,
+        collateral_type.decimals,
+        collateral_account.deposited_asset_amount,
+        current_debt,
+        0,
+        amount_to_mint,
+        collateral_type.liquidation_threshold,
+        config_account.min_health_factor,
+    )?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MINT_ACCOUNT,
+        &timestamp.to_le_bytes(),
+        &[ctx.bumps.mint_account],
+    ]];
+    //铸币手续费从本次铸造数量中扣除并铸造到国库，仓位债务仍按amount_to_mint计
+    let mint_fee =
+        calc_fee(amount_to_mint, config_account.mint_fee_bps).ok_or(ErrorCode::MathOverflow)?;
+    let amount_to_receive = amount_to_mint
+        .checked_sub(mint_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    mint_stable_coins(
+        amount_to_receive,
+        ctx.accounts.mint_account.to_account_info(),
+        ctx.accounts.receive_stablecoin_account.to_account_info(),
+        ctx.accounts.mint_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    if mint_fee > 0 {
+        mint_stable_coins(
+            mint_fee,
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.treasury_stablecoin_account.to_account_info(),
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        msg!("mint fee:{}", mint_fee);
+    }
+    msg!("mint against collateral completed!");
+
+    collateral_account.normalized_debt = collateral_account
+        .normalized_debt
+        .checked_add(normalized_debt_to_add)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_normalized_debt = total_normalized_debt;
+    config_account.total_normalized_debt = market_normalized_debt;
+
+    Ok(())
+}
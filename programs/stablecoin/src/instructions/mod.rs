@@ -1,11 +1,65 @@
 mod init_config;
 mod update_config;
+mod register_collateral_type;
+mod update_collateral_type;
 mod deposit_collateral_and_mint_tokens;
 mod burn_tokens_and_redeem_collateral;
+mod deposit_collateral;
+mod mint_against_collateral;
+mod repay_debt;
+mod withdraw_collateral;
 mod liquidate;
+mod accrue;
+mod withdraw_treasury;
+mod poke_price;
+mod resume;
+mod set_price_override;
+mod clear_price_override;
+mod propose_authority;
+mod accept_authority;
+mod renounce_authority;
+mod grant_role;
+mod revoke_role;
+mod set_pause_flags;
+mod queue_config_change;
+mod execute_config_change;
+mod cancel_config_change;
+mod create_multisig;
+mod propose_multisig_transaction;
+mod approve_multisig_transaction;
+mod execute_multisig_transaction;
+mod set_multisig_owners;
+mod get_market_totals;
 
 pub use init_config::*;
 pub use update_config::*;
+pub use register_collateral_type::*;
+pub use update_collateral_type::*;
 pub use deposit_collateral_and_mint_tokens::*;
 pub use burn_tokens_and_redeem_collateral::*;
-pub use liquidate::*;
\ No newline at end of file
+pub use deposit_collateral::*;
+pub use mint_against_collateral::*;
+pub use repay_debt::*;
+pub use withdraw_collateral::*;
+pub use liquidate::*;
+pub use accrue::*;
+pub use withdraw_treasury::*;
+pub use poke_price::*;
+pub use resume::*;
+pub use set_price_override::*;
+pub use clear_price_override::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use renounce_authority::*;
+pub use grant_role::*;
+pub use revoke_role::*;
+pub use set_pause_flags::*;
+pub use queue_config_change::*;
+pub use execute_config_change::*;
+pub use cancel_config_change::*;
+pub use create_multisig::*;
+pub use propose_multisig_transaction::*;
+pub use approve_multisig_transaction::*;
+pub use execute_multisig_transaction::*;
+pub use set_multisig_owners::*;
+pub use get_market_totals::*;
\ No newline at end of file
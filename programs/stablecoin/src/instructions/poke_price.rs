@@ -0,0 +1,107 @@
+use crate::constants::{
+    COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PRICE_HISTORY_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config, PriceHistory};
+use crate::utils::{
+    evaluate_circuit_breaker, get_oracle_collateral_price, load_price_sources,
+    trip_circuit_breaker, CircuitBreakerStatus,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///任何人都可以调用，从预言机读取当前价格写入抵押资产的价格历史账户
+///首次调用时创建价格历史账户，由调用人支付租金
+///新价格触发熔断时暂停市场的铸币和清算，该价格不写入价格历史
+///距上次记录的观测不足最小间隔时不写入，防止短时间内用即时价格覆盖整个价格历史
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct PokePrice<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PriceHistory::INIT_SPACE,
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
+    pub price_update: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn poke_price_handler(ctx: Context<PokePrice>, _timestamp: i64) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    let collateral_type_key = ctx.accounts.collateral_type.key();
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let now = Clock::get()?.unix_timestamp;
+    let price_sources = load_price_sources(
+        &ctx.accounts.price_update.to_account_info(),
+        ctx.remaining_accounts,
+        collateral_type,
+        config_account,
+    )?;
+    //价格历史只记录预言机价格
+    let collateral_price =
+        get_oracle_collateral_price(&price_sources, collateral_type, config_account)?;
+
+    let price_history = &mut ctx.accounts.price_history;
+    if price_history.init_time == 0 {
+        price_history.collateral_type = collateral_type.key();
+        price_history.self_bump = ctx.bumps.price_history;
+        price_history.init_time = now;
+    }
+
+    match evaluate_circuit_breaker(config_account, collateral_type, collateral_price.price, now)
+        .ok_or(ErrorCode::MathOverflow)?
+    {
+        CircuitBreakerStatus::Normal => {
+            collateral_type.last_accepted_price = collateral_price.price;
+            collateral_type.last_accepted_price_time = now;
+            let min_interval =
+                PriceHistory::min_observation_interval(config_account.twap_window_secs);
+            if price_history.push(now, collateral_price.price, min_interval) {
+                price_history.last_update_time = now;
+                msg!("recorded price:{} at {}", collateral_price.price, now);
+            }
+        }
+        CircuitBreakerStatus::Paused => {
+            msg!(
+                "circuit breaker paused until:{}",
+                config_account.circuit_breaker_paused_until
+            );
+        }
+        CircuitBreakerStatus::Tripped => {
+            trip_circuit_breaker(
+                config_account,
+                collateral_type,
+                collateral_type_key,
+                collateral_price.price,
+                now,
+            )?;
+        }
+    }
+
+    Ok(())
+}
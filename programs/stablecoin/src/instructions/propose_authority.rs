@@ -0,0 +1,44 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::events::AuthorityProposed;
+use crate::states::Config;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///管理员提议新的管理员，新管理员调用accept_authority后生效
+///传入Pubkey::default()时取消当前的提议
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = authority,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn propose_authority_handler(
+    ctx: Context<ProposeAuthority>,
+    _timestamp: i64,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    config_account.pending_authority = new_authority;
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(AuthorityProposed {
+        config_account: config_account.key(),
+        authority: config_account.authority,
+        pending_authority: new_authority,
+    });
+    msg!("authority proposed:{}", new_authority);
+
+    Ok(())
+}
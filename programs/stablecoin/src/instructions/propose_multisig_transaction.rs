@@ -0,0 +1,77 @@
+use crate::constants::{
+    MAX_MULTISIG_TRANSACTION_ACCOUNTS, MAX_MULTISIG_TRANSACTION_DATA, MULTISIG_ACCOUNT,
+    MULTISIG_TRANSACTION_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::events::MultisigTransactionProposed;
+use crate::states::{Multisig, MultisigTransaction, TransactionAccount};
+use anchor_lang::prelude::*;
+
+///多签成员提议一条指令(例如process_update_config)，提议人自动批准
+#[derive(Accounts)]
+pub struct ProposeMultisigTransaction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MULTISIG_ACCOUNT, multisig.creator.as_ref(), &multisig.multisig_id.to_le_bytes()],
+        bump = multisig.self_bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MultisigTransaction::INIT_SPACE,
+        seeds = [MULTISIG_TRANSACTION_ACCOUNT, multisig.key().as_ref(), &multisig.transaction_count.to_le_bytes()],
+        bump,
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_multisig_transaction_handler(
+    ctx: Context<ProposeMultisigTransaction>,
+    program_id: Pubkey,
+    accounts: Vec<TransactionAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let owner_index = multisig
+        .owner_index(&ctx.accounts.proposer.key())
+        .ok_or(ErrorCode::NotMultisigOwner)?;
+    if accounts.len() > MAX_MULTISIG_TRANSACTION_ACCOUNTS
+        || data.len() > MAX_MULTISIG_TRANSACTION_DATA
+    {
+        return Err(ErrorCode::MultisigTransactionTooLarge.into());
+    }
+    let mut signers = vec![false; multisig.owners.len()];
+    signers[owner_index] = true;
+
+    let transaction_index = multisig.transaction_count;
+    *ctx.accounts.transaction = MultisigTransaction {
+        multisig: multisig.key(),
+        proposer: ctx.accounts.proposer.key(),
+        program_id,
+        accounts,
+        data,
+        signers,
+        owner_set_seqno: multisig.owner_set_seqno,
+        executed: false,
+        self_bump: ctx.bumps.transaction,
+        init_time: Clock::get()?.unix_timestamp,
+    };
+    multisig.transaction_count = multisig
+        .transaction_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    emit!(MultisigTransactionProposed {
+        multisig: multisig.key(),
+        transaction: ctx.accounts.transaction.key(),
+        transaction_index,
+        proposer: ctx.accounts.proposer.key(),
+        program_id,
+    });
+    msg!("multisig transaction proposed, index:{}", transaction_index);
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use crate::constants::{
+    COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PENDING_CONFIG_CHANGE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::events::ConfigChangeQueued;
+use crate::states::{CollateralType, Config, PendingConfigChange, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RiskParamsChange {
+    pub max_ltv: Option<u64>,
+    pub liquidation_threshold: Option<u64>,
+    pub liquidation_bonus: Option<u64>,
+    pub min_health_factor: Option<u64>,
+    pub risk_param_timelock_secs: Option<u64>,
+}
+
+///风险管理员提议修改风险参数，经过risk_param_timelock_secs后才能执行
+///传入collateral_type时修改该抵押资产类型的max_ltv、清算阈值和清算奖励，否则修改市场级参数
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::RiskAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Option<Account<'info, CollateralType>>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingConfigChange::INIT_SPACE,
+        seeds = [
+            PENDING_CONFIG_CHANGE_ACCOUNT,
+            config_account.key().as_ref(),
+            collateral_type.as_ref().map(|c| c.key()).unwrap_or_default().as_ref(),
+        ],
+        bump,
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_config_change_handler(
+    ctx: Context<QueueConfigChange>,
+    _timestamp: i64,
+    params: RiskParamsChange,
+) -> Result<()> {
+    let config_account = &ctx.accounts.config_account;
+    if params.max_ltv.is_none()
+        && params.liquidation_threshold.is_none()
+        && params.liquidation_bonus.is_none()
+        && params.min_health_factor.is_none()
+        && params.risk_param_timelock_secs.is_none()
+    {
+        return Err(ErrorCode::InvalidConfigChange.into());
+    }
+    //排队时先按当前配置校验一次，执行时会再次校验
+    let collateral_type_key = match &ctx.accounts.collateral_type {
+        Some(collateral_type) => {
+            //健康因子和timelock是市场级参数，不能随抵押资产类型一起修改
+            if params.min_health_factor.is_some() || params.risk_param_timelock_secs.is_some() {
+                return Err(ErrorCode::InvalidConfigChange.into());
+            }
+            let mut preview = (**collateral_type).clone();
+            preview.max_ltv = params.max_ltv.unwrap_or(preview.max_ltv);
+            preview.liquidation_threshold = params
+                .liquidation_threshold
+                .unwrap_or(preview.liquidation_threshold);
+            preview.liquidation_bonus = params
+                .liquidation_bonus
+                .unwrap_or(preview.liquidation_bonus);
+            preview.validate_risk_parameters()?;
+            collateral_type.key()
+        }
+        None => {
+            let mut preview = (**config_account).clone();
+            preview.max_ltv = params.max_ltv.unwrap_or(preview.max_ltv);
+            preview.liquidation_threshold = params
+                .liquidation_threshold
+                .unwrap_or(preview.liquidation_threshold);
+            preview.liquidation_bonus = params
+                .liquidation_bonus
+                .unwrap_or(preview.liquidation_bonus);
+            preview.min_health_factor = params
+                .min_health_factor
+                .unwrap_or(preview.min_health_factor);
+            preview.validate_risk_parameters()?;
+            Pubkey::default()
+        }
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let eta = now
+        .checked_add(config_account.risk_param_timelock_secs as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    *ctx.accounts.pending_config_change = PendingConfigChange {
+        config_account: config_account.key(),
+        proposer: ctx.accounts.admin.key(),
+        collateral_type: collateral_type_key,
+        max_ltv: params.max_ltv,
+        liquidation_threshold: params.liquidation_threshold,
+        liquidation_bonus: params.liquidation_bonus,
+        min_health_factor: params.min_health_factor,
+        risk_param_timelock_secs: params.risk_param_timelock_secs,
+        queued_at: now,
+        eta,
+        self_bump: ctx.bumps.pending_config_change,
+    };
+    emit!(ConfigChangeQueued {
+        config_account: config_account.key(),
+        proposer: ctx.accounts.admin.key(),
+        collateral_type: collateral_type_key,
+        max_ltv: params.max_ltv,
+        liquidation_threshold: params.liquidation_threshold,
+        liquidation_bonus: params.liquidation_bonus,
+        min_health_factor: params.min_health_factor,
+        risk_param_timelock_secs: params.risk_param_timelock_secs,
+        eta,
+    });
+    msg!("config change queued, eta:{}", eta);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::constants::{COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+///注册一种可抵押资产(SPL Token或Token 2022)，每个市场每种资产一个CollateralType账户
+///抵押率、清算阈值和清算奖励默认取Config中的值，之后可通过update_collateral_type单独调整
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct RegisterCollateralType<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::RiskAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CollateralType::INIT_SPACE,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    pub system_program: Program<'info, System>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn register_collateral_type_handler(
+    ctx: Context<RegisterCollateralType>,
+    _timestamp: i64,
+    feed_id: String,
+    max_age: u64,
+    max_age_slots: u64,
+    debt_ceiling: u64,
+) -> Result<()> {
+    let feed_id = get_feed_id_from_hex(&feed_id)?;
+    let config_account = &ctx.accounts.config_account;
+    let now = Clock::get()?.unix_timestamp;
+    *ctx.accounts.collateral_type = CollateralType {
+        config_account: config_account.key(),
+        collateral_mint: ctx.accounts.collateral_mint.key(),
+        token_program: ctx.accounts.collateral_token_program.key(),
+        decimals: ctx.accounts.collateral_mint.decimals,
+        feed_id,
+        max_age,
+        max_age_slots,
+        secondary_oracles: Vec::new(),
+        max_ltv: config_account.max_ltv,
+        liquidation_threshold: config_account.liquidation_threshold,
+        liquidation_bonus: config_account.liquidation_bonus,
+        debt_ceiling,
+        total_normalized_debt: 0,
+        total_collateral: 0,
+        enabled: true,
+        last_accepted_price: 0,
+        last_accepted_price_time: 0,
+        price_override_expires_at: 0,
+        self_bump: ctx.bumps.collateral_type,
+        init_time: now,
+        last_update_time: now,
+    };
+    ctx.accounts.collateral_type.validate_risk_parameters()?;
+    ctx.accounts.collateral_type.validate_oracle_parameters()?;
+    ctx.accounts.config_account.collateral_type_count = ctx
+        .accounts
+        .config_account
+        .collateral_type_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    msg!(
+        "registered collateral type for mint:{}",
+        ctx.accounts.collateral_mint.key()
+    );
+    Ok(())
+}
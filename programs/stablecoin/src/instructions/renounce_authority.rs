@@ -0,0 +1,44 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::events::AuthorityTransferred;
+use crate::states::{Config, Roles};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///管理员放弃管理权限并撤销所有角色，之后市场配置不可再修改
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct RenounceAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = authority,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn renounce_authority_handler(ctx: Context<RenounceAuthority>, _timestamp: i64) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    let old_authority = config_account.authority;
+    //默认公钥没有对应的私钥，has_one = authority的指令都无法再通过
+    config_account.authority = Pubkey::default();
+    config_account.pending_authority = Pubkey::default();
+    //同时撤销所有角色，市场参数不能再被任何人修改
+    config_account.roles = Roles::new(Pubkey::default());
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(AuthorityTransferred {
+        config_account: config_account.key(),
+        old_authority,
+        new_authority: Pubkey::default(),
+    });
+    msg!("authority renounced");
+
+    Ok(())
+}
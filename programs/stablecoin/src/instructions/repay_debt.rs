@@ -0,0 +1,127 @@
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PAUSE_REPAY,
+};
+use crate::errors::ErrorCode;
+use crate::states::{Collateral, CollateralType, Config};
+use crate::utils::{
+    accrue_stability_fee, burn_tokens, calc_debt_from_normalized, calc_fee,
+    calc_normalized_debt_to_repay, pay_fee_to_treasury,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+///销毁稳定币偿还仓位债务，不赎回抵押资产
+///稳定币可以来自签名人控制的任意稳定币账户，不要求是仓位的接收账户
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct RepayDebt<'info> {
+    pub depositor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = treasury_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_account.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_ACCOUNT, collateral_account.collateral_mint.as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = collateral_account.self_bump,
+        has_one = depositor,
+        has_one = collateral_type,
+    )]
+    pub collateral_account: Account<'info, Collateral>,
+    #[account(
+        mut,
+        token::mint = mint_account,
+        token::authority = depositor,
+        token::token_program = token_program,
+    )]
+    pub depositor_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    pub treasury_account: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = treasury_account,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_stablecoin_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn repay_debt_handler(
+    ctx: Context<RepayDebt>,
+    _timestamp: i64,
+    amount_to_repay: u64,
+) -> Result<()> {
+    ctx.accounts.config_account.check_not_paused(PAUSE_REPAY)?;
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let debt_index = config_account.debt_index;
+    let current_debt = calc_debt_from_normalized(collateral_account.normalized_debt, debt_index)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if amount_to_repay > current_debt {
+        return Err(ErrorCode::RepayAmountExceedsDebt.into());
+    }
+    let normalized_debt_to_repay = calc_normalized_debt_to_repay(
+        amount_to_repay,
+        collateral_account.normalized_debt,
+        debt_index,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+
+    burn_tokens(
+        amount_to_repay,
+        ctx.accounts.mint_account.to_account_info(),
+        ctx.accounts.depositor_stablecoin_account.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    msg!("repay debt completed!");
+    //赎回手续费按销毁的稳定币数量计算，从同一稳定币账户转入国库
+    let redeem_fee =
+        calc_fee(amount_to_repay, config_account.redeem_fee_bps).ok_or(ErrorCode::MathOverflow)?;
+    if redeem_fee > 0 {
+        pay_fee_to_treasury(
+            redeem_fee,
+            ctx.accounts.mint_account.to_account_info(),
+            ctx.accounts.depositor_stablecoin_account.to_account_info(),
+            ctx.accounts.treasury_stablecoin_account.to_account_info(),
+            ctx.accounts.depositor.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        msg!("redeem fee:{}", redeem_fee);
+    }
+
+    collateral_account.normalized_debt = collateral_account
+        .normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_normalized_debt = collateral_type
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    config_account.total_normalized_debt = config_account
+        .total_normalized_debt
+        .checked_sub(normalized_debt_to_repay)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::states::{Config, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///pauser解除价格熔断，恢复铸币和清算
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct Resume<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::Pauser, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn resume_handler(ctx: Context<Resume>, _timestamp: i64) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    config_account.circuit_breaker_paused_until = 0;
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    msg!("circuit breaker resumed");
+
+    Ok(())
+}
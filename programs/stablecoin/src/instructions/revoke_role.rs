@@ -0,0 +1,41 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::events::RoleRevoked;
+use crate::states::{Config, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///authority撤销管理角色，撤销后该角色对应的操作都无法执行，直到重新授予
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct RevokeRole<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        has_one = authority,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, _timestamp: i64, role: Role) -> Result<()> {
+    let config_account = &mut ctx.accounts.config_account;
+    let holder = config_account.roles.holder_mut(role);
+    let previous_holder = *holder;
+    *holder = Pubkey::default();
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(RoleRevoked {
+        config_account: config_account.key(),
+        role,
+        previous_holder,
+    });
+    msg!("revoke role {:?} from {}", role, previous_holder);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::constants::{MULTISIG_ACCOUNT, MULTISIG_SIGNER_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::MultisigOwnersChanged;
+use crate::states::Multisig;
+use anchor_lang::prelude::*;
+
+///修改多签成员和阈值，只能由多签自身通过execute_multisig_transaction调用
+#[derive(Accounts)]
+pub struct SetMultisigOwners<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_ACCOUNT, multisig.creator.as_ref(), &multisig.multisig_id.to_le_bytes()],
+        bump = multisig.self_bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [MULTISIG_SIGNER_ACCOUNT, multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+pub fn set_multisig_owners_handler(
+    ctx: Context<SetMultisigOwners>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.validate_owners()?;
+    //成员变更后之前提议的交易不能再批准或执行
+    multisig.owner_set_seqno = multisig
+        .owner_set_seqno
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    emit!(MultisigOwnersChanged {
+        multisig: multisig.key(),
+        owners: multisig.owners.clone(),
+        threshold,
+        owner_set_seqno: multisig.owner_set_seqno,
+    });
+    msg!("multisig owners changed");
+
+    Ok(())
+}
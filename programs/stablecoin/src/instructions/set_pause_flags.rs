@@ -0,0 +1,49 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::events::PauseFlagsUpdated;
+use crate::states::{Config, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///pauser设置各操作的暂停标志，pause_flags为PAUSE_DEPOSIT等标志位的组合，0表示全部恢复
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct SetPauseFlags<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::Pauser, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+}
+
+pub fn set_pause_flags_handler(
+    ctx: Context<SetPauseFlags>,
+    _timestamp: i64,
+    pause_flags: u8,
+) -> Result<()> {
+    if pause_flags & !crate::PAUSE_ALL != 0 {
+        return Err(ErrorCode::InvalidPauseFlags.into());
+    }
+    let config_account = &mut ctx.accounts.config_account;
+    let old_flags = config_account.pause_flags;
+    config_account.pause_flags = pause_flags;
+    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    emit!(PauseFlagsUpdated {
+        config_account: config_account.key(),
+        admin: ctx.accounts.admin.key(),
+        old_flags,
+        new_flags: pause_flags,
+    });
+    msg!("pause flags:{:#07b}", pause_flags);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::constants::{
+    COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::events::PriceOverrideSet;
+use crate::states::{CollateralType, Config, PriceOverride, Role};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+///预言机管理员设置抵押资产的紧急覆盖价格
+///经过price_override_timelock_secs后生效，有效期不超过max_price_override_duration_secs
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct SetPriceOverride<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::OracleAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PriceOverride::INIT_SPACE,
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump,
+    )]
+    pub price_override: Account<'info, PriceOverride>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_price_override_handler(
+    ctx: Context<SetPriceOverride>,
+    _timestamp: i64,
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    duration_secs: u64,
+) -> Result<()> {
+    let config_account = &ctx.accounts.config_account;
+    if price <= 0
+        || duration_secs == 0
+        || duration_secs > config_account.max_price_override_duration_secs
+    {
+        return Err(ErrorCode::InvalidPriceOverride.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let effective_at = now
+        .checked_add(config_account.price_override_timelock_secs as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let expires_at = effective_at
+        .checked_add(duration_secs as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    *ctx.accounts.price_override = PriceOverride {
+        config_account: config_account.key(),
+        collateral_type: ctx.accounts.collateral_type.key(),
+        price,
+        conf,
+        exponent,
+        set_time: now,
+        effective_at,
+        expires_at,
+        self_bump: ctx.bumps.price_override,
+    };
+    ctx.accounts.collateral_type.price_override_expires_at = expires_at;
+    emit!(PriceOverrideSet {
+        config_account: config_account.key(),
+        collateral_type: ctx.accounts.collateral_type.key(),
+        price,
+        conf,
+        exponent,
+        effective_at,
+        expires_at,
+    });
+    msg!(
+        "price override set, effective at:{}, expires at:{}",
+        effective_at,
+        expires_at
+    );
+
+    Ok(())
+}
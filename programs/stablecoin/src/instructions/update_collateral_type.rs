@@ -0,0 +1,85 @@
+use crate::constants::{COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, MINT_ACCOUNT};
+use crate::states::{CollateralType, Config, Role, SecondaryOracle};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateCollateralTypeParams {
+    pub feed_id: Option<String>,
+    pub max_age: Option<u64>,
+    pub max_age_slots: Option<u64>,
+    pub secondary_oracles: Option<Vec<SecondaryOracle>>,
+    pub debt_ceiling: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+///修改抵押资产类型的预言机、债务上限和启用状态，max_ltv、清算阈值和清算奖励通过queue_config_change修改
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct UpdateCollateralType<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_type.collateral_mint.as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+}
+
+pub fn update_collateral_type_handler(
+    ctx: Context<UpdateCollateralType>,
+    _timestamp: i64,
+    params: UpdateCollateralTypeParams,
+) -> Result<()> {
+    let roles = &ctx.accounts.config_account.roles;
+    let admin = ctx.accounts.admin.key();
+    let collateral_type = &mut ctx.accounts.collateral_type;
+
+    if let Some(feed_id) = params.feed_id {
+        roles.check_role(Role::OracleAdmin, &admin)?;
+        collateral_type.feed_id = get_feed_id_from_hex(&feed_id)?;
+    }
+
+    if let Some(max_age) = params.max_age {
+        roles.check_role(Role::OracleAdmin, &admin)?;
+        collateral_type.max_age = max_age;
+    }
+
+    if let Some(max_age_slots) = params.max_age_slots {
+        roles.check_role(Role::OracleAdmin, &admin)?;
+        collateral_type.max_age_slots = max_age_slots;
+    }
+
+    if let Some(secondary_oracles) = params.secondary_oracles {
+        roles.check_role(Role::OracleAdmin, &admin)?;
+        collateral_type.secondary_oracles = secondary_oracles;
+    }
+
+    if let Some(debt_ceiling) = params.debt_ceiling {
+        roles.check_role(Role::MinterCapAdmin, &admin)?;
+        collateral_type.debt_ceiling = debt_ceiling;
+    }
+
+    if let Some(enabled) = params.enabled {
+        roles.check_role(Role::Pauser, &admin)?;
+        collateral_type.enabled = enabled;
+    }
+
+    collateral_type.validate_oracle_parameters()?;
+    collateral_type.last_update_time = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
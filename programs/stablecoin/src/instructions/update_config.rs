@@ -1,13 +1,71 @@
 use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT};
-use crate::states::Config;
+use crate::errors::ErrorCode;
+use crate::states::{Config, OracleAggregation, OracleSource, PriceMode, Role};
+use crate::utils::{accrue_stability_fee, set_config_value, ConfigAudit};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateConfigParams {
+    pub stability_fee_bps: Option<u64>,
+    pub debt_ceiling: Option<u64>,
+    pub mint_fee_bps: Option<u64>,
+    pub redeem_fee_bps: Option<u64>,
+    pub liquidation_protocol_share_bps: Option<u64>,
+    pub close_factor_bps: Option<u64>,
+    pub oracle_conf_multiplier_bps: Option<u64>,
+    pub max_oracle_conf_bps: Option<u64>,
+    pub require_full_verification: Option<bool>,
+    pub oracle_source: Option<OracleSource>,
+    pub oracle_receiver_program: Option<Pubkey>,
+    pub price_signer: Option<Pubkey>,
+    pub oracle_aggregation: Option<OracleAggregation>,
+    pub max_oracle_deviation_bps: Option<u64>,
+    pub twap_window_secs: Option<u64>,
+    pub mint_price_mode: Option<PriceMode>,
+    pub redeem_price_mode: Option<PriceMode>,
+    pub liquidation_price_mode: Option<PriceMode>,
+    pub circuit_breaker_bps: Option<u64>,
+    pub circuit_breaker_window_secs: Option<u64>,
+    pub circuit_breaker_cooldown_secs: Option<u64>,
+    pub price_override_timelock_secs: Option<u64>,
+    pub max_price_override_duration_secs: Option<u64>,
+}
+
+impl UpdateConfigParams {
+    ///所有字段都为None时没有要修改的配置
+    pub fn is_empty(&self) -> bool {
+        self.stability_fee_bps.is_none()
+            && self.debt_ceiling.is_none()
+            && self.mint_fee_bps.is_none()
+            && self.redeem_fee_bps.is_none()
+            && self.liquidation_protocol_share_bps.is_none()
+            && self.close_factor_bps.is_none()
+            && self.oracle_conf_multiplier_bps.is_none()
+            && self.max_oracle_conf_bps.is_none()
+            && self.require_full_verification.is_none()
+            && self.oracle_source.is_none()
+            && self.oracle_receiver_program.is_none()
+            && self.price_signer.is_none()
+            && self.oracle_aggregation.is_none()
+            && self.max_oracle_deviation_bps.is_none()
+            && self.twap_window_secs.is_none()
+            && self.mint_price_mode.is_none()
+            && self.redeem_price_mode.is_none()
+            && self.liquidation_price_mode.is_none()
+            && self.circuit_breaker_bps.is_none()
+            && self.circuit_breaker_window_secs.is_none()
+            && self.circuit_breaker_cooldown_secs.is_none()
+            && self.price_override_timelock_secs.is_none()
+            && self.max_price_override_duration_secs.is_none()
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(timestamp:i64)]
 pub struct UpdateConfig<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub admin: Signer<'info>,
     #[account(
         mut,
         seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
@@ -17,7 +75,8 @@ pub struct UpdateConfig<'info> {
     #[account(
         mut,
         seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
-        bump = config_account.self_bump
+        bump = config_account.self_bump,
+        has_one = mint_account,
     )]
     pub config_account: Account<'info, Config>,
 }
@@ -25,26 +84,257 @@ pub struct UpdateConfig<'info> {
 pub fn update_config_handler(
     ctx: Context<UpdateConfig>,
     _timestamp: i64,
-    liquidation_threshold: Option<u64>,
-    liquidation_bonus: Option<u64>,
-    min_health_factor: Option<u64>,
-    
+    params: UpdateConfigParams,
 ) -> Result<()> {
+    if params.is_empty() {
+        return Err(ErrorCode::InvalidConfigChange.into());
+    }
     let config_account = &mut ctx.accounts.config_account;
+    let now = Clock::get()?.unix_timestamp;
+    let audit = ConfigAudit {
+        config_account: config_account.key(),
+        collateral_type: Pubkey::default(),
+        admin: ctx.accounts.admin.key(),
+        roles: config_account.roles,
+        update_time: now,
+    };
+
+    if let Some(fee) = params.stability_fee_bps {
+        //先按旧费率累计到当前时间，新费率只对之后的时间生效
+        accrue_stability_fee(config_account, now)?;
+        set_config_value(
+            &mut config_account.stability_fee_bps,
+            fee,
+            "stability_fee_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(debt_ceiling) = params.debt_ceiling {
+        set_config_value(
+            &mut config_account.debt_ceiling,
+            debt_ceiling,
+            "debt_ceiling",
+            Role::MinterCapAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(fee) = params.mint_fee_bps {
+        set_config_value(
+            &mut config_account.mint_fee_bps,
+            fee,
+            "mint_fee_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(fee) = params.redeem_fee_bps {
+        set_config_value(
+            &mut config_account.redeem_fee_bps,
+            fee,
+            "redeem_fee_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(share) = params.liquidation_protocol_share_bps {
+        set_config_value(
+            &mut config_account.liquidation_protocol_share_bps,
+            share,
+            "liquidation_protocol_share_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(close_factor) = params.close_factor_bps {
+        set_config_value(
+            &mut config_account.close_factor_bps,
+            close_factor,
+            "close_factor_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(multiplier) = params.oracle_conf_multiplier_bps {
+        set_config_value(
+            &mut config_account.oracle_conf_multiplier_bps,
+            multiplier,
+            "oracle_conf_multiplier_bps",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(max_conf) = params.max_oracle_conf_bps {
+        set_config_value(
+            &mut config_account.max_oracle_conf_bps,
+            max_conf,
+            "max_oracle_conf_bps",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(require_full) = params.require_full_verification {
+        set_config_value(
+            &mut config_account.require_full_verification,
+            require_full,
+            "require_full_verification",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(source) = params.oracle_source {
+        set_config_value(
+            &mut config_account.oracle_source,
+            source,
+            "oracle_source",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(program) = params.oracle_receiver_program {
+        set_config_value(
+            &mut config_account.oracle_receiver_program,
+            program,
+            "oracle_receiver_program",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(signer) = params.price_signer {
+        set_config_value(
+            &mut config_account.price_signer,
+            signer,
+            "price_signer",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(aggregation) = params.oracle_aggregation {
+        set_config_value(
+            &mut config_account.oracle_aggregation,
+            aggregation,
+            "oracle_aggregation",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(deviation) = params.max_oracle_deviation_bps {
+        set_config_value(
+            &mut config_account.max_oracle_deviation_bps,
+            deviation,
+            "max_oracle_deviation_bps",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(window) = params.twap_window_secs {
+        set_config_value(
+            &mut config_account.twap_window_secs,
+            window,
+            "twap_window_secs",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(mode) = params.mint_price_mode {
+        set_config_value(
+            &mut config_account.mint_price_mode,
+            mode,
+            "mint_price_mode",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(mode) = params.redeem_price_mode {
+        set_config_value(
+            &mut config_account.redeem_price_mode,
+            mode,
+            "redeem_price_mode",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(mode) = params.liquidation_price_mode {
+        set_config_value(
+            &mut config_account.liquidation_price_mode,
+            mode,
+            "liquidation_price_mode",
+            Role::OracleAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(bps) = params.circuit_breaker_bps {
+        set_config_value(
+            &mut config_account.circuit_breaker_bps,
+            bps,
+            "circuit_breaker_bps",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
+
+    if let Some(window) = params.circuit_breaker_window_secs {
+        set_config_value(
+            &mut config_account.circuit_breaker_window_secs,
+            window,
+            "circuit_breaker_window_secs",
+            Role::RiskAdmin,
+            &audit,
+        )?;
+    }
 
-    if let Some(threshold) = liquidation_threshold {
-        config_account.liquidation_threshold = threshold;
+    if let Some(cooldown) = params.circuit_breaker_cooldown_secs {
+        set_config_value(
+            &mut config_account.circuit_breaker_cooldown_secs,
+            cooldown,
+            "circuit_breaker_cooldown_secs",
+            Role::RiskAdmin,
+            &audit,
+        )?;
     }
 
-    if let Some(bonus) = liquidation_bonus {
-        config_account.liquidation_bonus = bonus;
+    if let Some(timelock) = params.price_override_timelock_secs {
+        set_config_value(
+            &mut config_account.price_override_timelock_secs,
+            timelock,
+            "price_override_timelock_secs",
+            Role::RiskAdmin,
+            &audit,
+        )?;
     }
 
-    if let Some(factor) = min_health_factor {
-        config_account.min_health_factor = factor;
+    if let Some(duration) = params.max_price_override_duration_secs {
+        set_config_value(
+            &mut config_account.max_price_override_duration_secs,
+            duration,
+            "max_price_override_duration_secs",
+            Role::RiskAdmin,
+            &audit,
+        )?;
     }
 
-    config_account.last_update_time = Clock::get()?.unix_timestamp;
+    config_account.validate_risk_parameters()?;
+    config_account.validate_fee_parameters()?;
+    config_account.validate_oracle_parameters()?;
+    config_account.last_update_time = now;
 
     Ok(())
 }
@@ -0,0 +1,168 @@
+use crate::constants::{
+    COLLATERAL_ACCOUNT, COLLATERAL_TYPE_ACCOUNT, CONFIG_ACCOUNT, DEPOSIT_ASSET_ACCOUNT,
+    MINT_ACCOUNT, PAUSE_WITHDRAW, PRICE_HISTORY_ACCOUNT, PRICE_OVERRIDE_ACCOUNT,
+};
+use crate::errors::ErrorCode;
+use crate::states::{Collateral, CollateralType, Config, PriceHistory, PriceOverride};
+use crate::utils::{
+    accrue_stability_fee, calc_debt_from_normalized,
+    check_health_factor_when_burn_tokens_and_redeem_collateral, get_collateral_price,
+    load_price_sources, redeem_or_withdraw_collateral, select_collateral_price,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+///从仓位中取回部分抵押资产，不偿还债务
+///仓位没有债务时不需要检查健康因子
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct WithdrawCollateral<'info> {
+    pub depositor: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_ACCOUNT, config_account.key().as_ref(), collateral_mint.key().as_ref()],
+        bump = collateral_type.self_bump,
+        has_one = config_account,
+        has_one = collateral_mint,
+    )]
+    pub collateral_type: Account<'info, CollateralType>,
+    #[account(
+        mint::token_program = collateral_token_program,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [COLLATERAL_ACCOUNT, collateral_mint.key().as_ref(), depositor.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = collateral_account.self_bump,
+        has_one = depositor,
+        has_one = collateral_type,
+        has_one = collateral_mint,
+        has_one = deposited_asset_account,
+    )]
+    pub collateral_account: Account<'info, Collateral>,
+    #[account(
+        mut,
+        seeds = [DEPOSIT_ASSET_ACCOUNT, collateral_account.key().as_ref()],
+        bump = collateral_account.deposited_asset_account_bump,
+    )]
+    pub deposited_asset_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = depositor,
+        token::token_program = collateral_token_program,
+    )]
+    pub depositor_collateral_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Pyth/Switchboard price account or the instructions sysvar, validated by load_price_sources within the handler.
+    pub price_update: UncheckedAccount<'info>,
+    //计算健康因子使用TWAP时需要传入
+    #[account(
+        seeds = [PRICE_HISTORY_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_history.self_bump,
+    )]
+    pub price_history: Option<Account<'info, PriceHistory>>,
+    //预言机价格不可用时使用的管理员覆盖价格
+    #[account(
+        seeds = [PRICE_OVERRIDE_ACCOUNT, collateral_type.key().as_ref()],
+        bump = price_override.self_bump,
+    )]
+    pub price_override: Option<Account<'info, PriceOverride>>,
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_collateral_handler(
+    ctx: Context<WithdrawCollateral>,
+    timestamp: i64,
+    amount_to_withdraw: u64,
+) -> Result<()> {
+    ctx.accounts.config_account.check_not_paused(PAUSE_WITHDRAW)?;
+    let collateral_account = &mut ctx.accounts.collateral_account;
+    let collateral_type = &mut ctx.accounts.collateral_type;
+    if amount_to_withdraw > collateral_account.deposited_asset_amount {
+        return Err(ErrorCode::WithdrawAmountExceedsCollateral.into());
+    }
+    let config_account = &mut ctx.accounts.config_account;
+    accrue_stability_fee(config_account, Clock::get()?.unix_timestamp)?;
+    let current_debt =
+        calc_debt_from_normalized(collateral_account.normalized_debt, config_account.debt_index)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+    if current_debt > 0 {
+        let price_sources = load_price_sources(
+            &ctx.accounts.price_update.to_account_info(),
+            ctx.remaining_accounts,
+            collateral_type,
+            config_account,
+        )?;
+
+        let collateral_price = get_collateral_price(
+            &price_sources,
+            ctx.accounts.price_override.as_deref(),
+            collateral_type,
+            config_account,
+        )?;
+        let risk_price = select_collateral_price(
+            collateral_price,
+            ctx.accounts.price_history.as_deref(),
+            config_account.redeem_price_mode,
+            config_account,
+            Clock::get()?.unix_timestamp,
+        )?;
+        check_health_factor_when_burn_tokens_and_redeem_collateral(
+            risk_price.lower,
+            collateral_type.decimals,
+            collateral_account.deposited_asset_amount,
+            current_debt,
+            amount_to_withdraw,
+            0,
+            collateral_type.liquidation_threshold,
+            config_account.min_health_factor,
+        )?;
+        msg!("Health check passed!");
+    }
+
+    let collateral_mint_key = ctx.accounts.collateral_mint.key();
+    let depositor_key = ctx.accounts.depositor.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        COLLATERAL_ACCOUNT,
+        collateral_mint_key.as_ref(),
+        depositor_key.as_ref(),
+        &timestamp.to_le_bytes(),
+        &[collateral_account.self_bump],
+    ]];
+    redeem_or_withdraw_collateral(
+        amount_to_withdraw,
+        collateral_type.decimals,
+        ctx.accounts.collateral_mint.to_account_info(),
+        ctx.accounts.deposited_asset_account.to_account_info(),
+        ctx.accounts.depositor_collateral_account.to_account_info(),
+        collateral_account.to_account_info(),
+        signer_seeds,
+        ctx.accounts.collateral_token_program.to_account_info(),
+    )?;
+    msg!("withdraw collateral completed!");
+
+    collateral_account.deposited_asset_amount = collateral_account
+        .deposited_asset_amount
+        .checked_sub(amount_to_withdraw)
+        .ok_or(ErrorCode::MathOverflow)?;
+    collateral_account.last_update_time = Clock::get()?.unix_timestamp;
+    collateral_type.total_collateral = collateral_type
+        .total_collateral
+        .checked_sub(amount_to_withdraw)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::constants::{CONFIG_ACCOUNT, MINT_ACCOUNT, TREASURY_ACCOUNT};
+use crate::errors::ErrorCode;
+use crate::states::{Config, Role};
+use crate::utils::redeem_or_withdraw_collateral;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+///国库管理员提取国库资产
+///传入token相关账户时提取国库持有的token(稳定币手续费或清算分成的抵押资产)，否则提取国库PDA中的SOL
+#[derive(Accounts)]
+#[instruction(timestamp: i64)]
+pub struct WithdrawTreasury<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MINT_ACCOUNT, &timestamp.to_le_bytes()],
+        bump
+    )]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [CONFIG_ACCOUNT, mint_account.key().as_ref(), &timestamp.to_le_bytes()],
+        bump = config_account.self_bump,
+        has_one = mint_account,
+        constraint = config_account.roles.has_role(Role::TreasuryAdmin, &admin.key()) @ ErrorCode::Unauthorized,
+        has_one = treasury_account,
+    )]
+    pub config_account: Account<'info, Config>,
+    #[account(mut)]
+    pub treasury_account: SystemAccount<'info>,
+    //提取SOL时的接收账户
+    #[account(mut)]
+    pub recipient: Option<SystemAccount<'info>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdraw_treasury_handler(
+    ctx: Context<WithdrawTreasury>,
+    _timestamp: i64,
+    amount: u64,
+) -> Result<()> {
+    let config_key = ctx.accounts.config_account.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        TREASURY_ACCOUNT,
+        config_key.as_ref(),
+        &[ctx.accounts.config_account.treasury_bump],
+    ]];
+
+    match (
+        &ctx.accounts.token_mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.token_program,
+    ) {
+        (
+            Some(token_mint),
+            Some(treasury_token_account),
+            Some(destination),
+            Some(token_program),
+        ) => {
+            if treasury_token_account.owner != ctx.accounts.treasury_account.key()
+                || treasury_token_account.mint != token_mint.key()
+                || destination.mint != token_mint.key()
+            {
+                return Err(ErrorCode::InvalidTreasuryWithdrawAccounts.into());
+            }
+            redeem_or_withdraw_collateral(
+                amount,
+                token_mint.decimals,
+                token_mint.to_account_info(),
+                treasury_token_account.to_account_info(),
+                destination.to_account_info(),
+                ctx.accounts.treasury_account.to_account_info(),
+                signer_seeds,
+                token_program.to_account_info(),
+            )?;
+            msg!(
+                "withdraw {} of token {} from treasury",
+                amount,
+                token_mint.key()
+            );
+        }
+        (None, None, None, None) => {
+            let recipient = ctx
+                .accounts
+                .recipient
+                .as_ref()
+                .ok_or(ErrorCode::InvalidTreasuryWithdrawAccounts)?;
+            //国库PDA需要保留免租金额
+            let withdrawable = ctx
+                .accounts
+                .treasury_account
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            if amount > withdrawable {
+                return Err(ErrorCode::InvalidTreasuryWithdrawAccounts.into());
+            }
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury_account.to_account_info(),
+                    to: recipient.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(cpi_ctx, amount)?;
+            msg!("withdraw {} lamports from treasury", amount);
+        }
+        _ => return Err(ErrorCode::InvalidTreasuryWithdrawAccounts.into()),
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod states;
 pub mod utils;
@@ -8,6 +9,7 @@ use anchor_lang::prelude::*;
 
 pub use constants::*;
 pub use errors::ErrorCode;
+pub use events::*;
 pub use instructions::*;
 pub use states::*;
 pub use utils::*;
@@ -21,43 +23,57 @@ pub mod stablecoin {
     pub fn process_init_config(
         ctx: Context<InitConfig>,
         timestamp: i64,
-        max_ltv: u64,
-        liquidation_threshold: u64,
-        liquidation_bonus: u64,
-        min_health_factor: u64,
+        params: InitConfigParams,
     ) -> Result<()> {
-        init_config_handler(
-            ctx,
-            timestamp,
-            max_ltv,
-            liquidation_threshold,
-            liquidation_bonus,
-            min_health_factor,
-        )
+        init_config_handler(ctx, timestamp, params)
     }
 
     pub fn process_update_config(
         ctx: Context<UpdateConfig>,
         timestamp: i64,
-        liquidation_threshold: Option<u64>,
-        liquidation_bonus: Option<u64>,
-        min_health_factor: Option<u64>
+        params: UpdateConfigParams,
+    ) -> Result<()> {
+        update_config_handler(ctx, timestamp, params)
+    }
+
+    pub fn process_register_collateral_type(
+        ctx: Context<RegisterCollateralType>,
+        timestamp: i64,
+        feed_id: String,
+        max_age: u64,
+        max_age_slots: u64,
+        debt_ceiling: u64,
     ) -> Result<()> {
-        update_config_handler(
+        register_collateral_type_handler(
             ctx,
             timestamp,
-            liquidation_threshold,
-            liquidation_bonus,
-            min_health_factor
+            feed_id,
+            max_age,
+            max_age_slots,
+            debt_ceiling,
         )
     }
 
+    pub fn process_update_collateral_type(
+        ctx: Context<UpdateCollateralType>,
+        timestamp: i64,
+        params: UpdateCollateralTypeParams,
+    ) -> Result<()> {
+        update_collateral_type_handler(ctx, timestamp, params)
+    }
+
     pub fn process_deposit_and_mint(
         ctx: Context<DepositCollateralAndMintTokens>,
         timestamp: i64,
         amount_to_deposit: u64,
+        amount_to_mint: u64,
     ) -> Result<()> {
-        deposit_collateral_and_mint_tokens_handler(ctx,timestamp, amount_to_deposit)
+        deposit_collateral_and_mint_tokens_handler(
+            ctx,
+            timestamp,
+            amount_to_deposit,
+            amount_to_mint,
+        )
     }
 
     pub fn process_burn_and_redeem(
@@ -68,6 +84,38 @@ pub mod stablecoin {
         burn_and_redeem_handler(ctx, timestamp, amount_to_bun)
     }
 
+    pub fn process_deposit_collateral(
+        ctx: Context<DepositCollateral>,
+        timestamp: i64,
+        amount_to_deposit: u64,
+    ) -> Result<()> {
+        deposit_collateral_handler(ctx, timestamp, amount_to_deposit)
+    }
+
+    pub fn process_mint_against_collateral(
+        ctx: Context<MintAgainstCollateral>,
+        timestamp: i64,
+        amount_to_mint: u64,
+    ) -> Result<()> {
+        mint_against_collateral_handler(ctx, timestamp, amount_to_mint)
+    }
+
+    pub fn process_repay_debt(
+        ctx: Context<RepayDebt>,
+        timestamp: i64,
+        amount_to_repay: u64,
+    ) -> Result<()> {
+        repay_debt_handler(ctx, timestamp, amount_to_repay)
+    }
+
+    pub fn process_withdraw_collateral(
+        ctx: Context<WithdrawCollateral>,
+        timestamp: i64,
+        amount_to_withdraw: u64,
+    ) -> Result<()> {
+        withdraw_collateral_handler(ctx, timestamp, amount_to_withdraw)
+    }
+
     pub fn process_liquidate(
         ctx: Context<Liquidation>,
         timestamp: i64,
@@ -75,4 +123,149 @@ pub mod stablecoin {
     ) -> Result<()> {
         liquidator_handler(ctx, timestamp, amount_to_burn)
     }
+
+    pub fn process_accrue(ctx: Context<Accrue>, timestamp: i64) -> Result<()> {
+        accrue_handler(ctx, timestamp)
+    }
+
+    pub fn process_withdraw_treasury(
+        ctx: Context<WithdrawTreasury>,
+        timestamp: i64,
+        amount: u64,
+    ) -> Result<()> {
+        withdraw_treasury_handler(ctx, timestamp, amount)
+    }
+
+    pub fn process_poke_price(ctx: Context<PokePrice>, timestamp: i64) -> Result<()> {
+        poke_price_handler(ctx, timestamp)
+    }
+
+    pub fn process_resume(ctx: Context<Resume>, timestamp: i64) -> Result<()> {
+        resume_handler(ctx, timestamp)
+    }
+
+    pub fn process_set_price_override(
+        ctx: Context<SetPriceOverride>,
+        timestamp: i64,
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        duration_secs: u64,
+    ) -> Result<()> {
+        set_price_override_handler(ctx, timestamp, price, conf, exponent, duration_secs)
+    }
+
+    pub fn process_clear_price_override(
+        ctx: Context<ClearPriceOverride>,
+        timestamp: i64,
+    ) -> Result<()> {
+        clear_price_override_handler(ctx, timestamp)
+    }
+
+    pub fn process_propose_authority(
+        ctx: Context<ProposeAuthority>,
+        timestamp: i64,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        propose_authority_handler(ctx, timestamp, new_authority)
+    }
+
+    pub fn process_accept_authority(ctx: Context<AcceptAuthority>, timestamp: i64) -> Result<()> {
+        accept_authority_handler(ctx, timestamp)
+    }
+
+    pub fn process_renounce_authority(
+        ctx: Context<RenounceAuthority>,
+        timestamp: i64,
+    ) -> Result<()> {
+        renounce_authority_handler(ctx, timestamp)
+    }
+
+    pub fn process_grant_role(
+        ctx: Context<GrantRole>,
+        timestamp: i64,
+        role: Role,
+        grantee: Pubkey,
+    ) -> Result<()> {
+        grant_role_handler(ctx, timestamp, role, grantee)
+    }
+
+    pub fn process_revoke_role(ctx: Context<RevokeRole>, timestamp: i64, role: Role) -> Result<()> {
+        revoke_role_handler(ctx, timestamp, role)
+    }
+
+    pub fn process_set_pause_flags(
+        ctx: Context<SetPauseFlags>,
+        timestamp: i64,
+        pause_flags: u8,
+    ) -> Result<()> {
+        set_pause_flags_handler(ctx, timestamp, pause_flags)
+    }
+
+    pub fn process_queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        timestamp: i64,
+        params: RiskParamsChange,
+    ) -> Result<()> {
+        queue_config_change_handler(ctx, timestamp, params)
+    }
+
+    pub fn process_execute_config_change(
+        ctx: Context<ExecuteConfigChange>,
+        timestamp: i64,
+    ) -> Result<()> {
+        execute_config_change_handler(ctx, timestamp)
+    }
+
+    pub fn process_cancel_config_change(
+        ctx: Context<CancelConfigChange>,
+        timestamp: i64,
+    ) -> Result<()> {
+        cancel_config_change_handler(ctx, timestamp)
+    }
+
+    pub fn process_create_multisig(
+        ctx: Context<CreateMultisig>,
+        multisig_id: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        create_multisig_handler(ctx, multisig_id, owners, threshold)
+    }
+
+    pub fn process_propose_multisig_transaction(
+        ctx: Context<ProposeMultisigTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        propose_multisig_transaction_handler(ctx, program_id, accounts, data)
+    }
+
+    pub fn process_approve_multisig_transaction(
+        ctx: Context<ApproveMultisigTransaction>,
+    ) -> Result<()> {
+        approve_multisig_transaction_handler(ctx)
+    }
+
+    pub fn process_execute_multisig_transaction(
+        ctx: Context<ExecuteMultisigTransaction>,
+    ) -> Result<()> {
+        execute_multisig_transaction_handler(ctx)
+    }
+
+    pub fn process_set_multisig_owners(
+        ctx: Context<SetMultisigOwners>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        set_multisig_owners_handler(ctx, owners, threshold)
+    }
+
+    pub fn process_get_market_totals(
+        ctx: Context<GetMarketTotals>,
+        timestamp: i64,
+    ) -> Result<MarketTotals> {
+        get_market_totals_handler(ctx, timestamp)
+    }
 }
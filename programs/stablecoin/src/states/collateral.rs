@@ -5,14 +5,19 @@ use anchor_lang::prelude::*;
 pub struct Collateral {
     //资产抵押人
     pub depositor: Pubkey,
-    //抵押资产账户
+    //抵押资产类型账户
+    pub collateral_type: Pubkey,
+    //抵押资产mint地址
+    pub collateral_mint: Pubkey,
+    //抵押资产金库账户(由本账户签名的token account)
     pub deposited_asset_account: Pubkey,
     //接收稳定币账户
     pub receive_stablecoin_account: Pubkey,
-    //抵押资产的lamports数量
-    pub deposited_asset_lamports: u64,
-    //稳定币铸造数量
-    pub stablecoin_minted_amount: u64,
+    //抵押资产数量(按抵押资产最小单位计)，只随本程序指令变化，与金库余额无关
+    pub deposited_asset_amount: u64,
+    //本金化债务，实际债务 = normalized_debt * Config.debt_index / DEBT_INDEX_PRECISION
+    //只随本程序指令变化，与钱包稳定币余额无关
+    pub normalized_debt: u64,
     pub self_bump: u8,
     pub deposited_asset_account_bump: u8,
     //是否初始化标志，避免意外覆盖某些值
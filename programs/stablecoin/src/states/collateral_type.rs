@@ -0,0 +1,90 @@
+use crate::constants::MAX_SECONDARY_ORACLES;
+use crate::states::OracleSource;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CollateralType {
+    //所属市场配置账户
+    pub config_account: Pubkey,
+    //抵押资产mint地址
+    pub collateral_mint: Pubkey,
+    //抵押资产所属token program(spl token或token 2022)
+    pub token_program: Pubkey,
+    //抵押资产精度
+    pub decimals: u8,
+    //抵押资产对应的pyth价格feed id
+    pub feed_id: [u8; 32],
+    //价格最大延迟(秒)
+    pub max_age: u64,
+    //价格账户最大延迟(slot)，0表示不按slot检查
+    pub max_age_slots: u64,
+    //备用预言机，每次取价都必须按顺序通过remaining_accounts全部传入
+    #[max_len(MAX_SECONDARY_ORACLES)]
+    pub secondary_oracles: Vec<SecondaryOracle>,
+    //抵押物最大抵押率
+    pub max_ltv: u64,
+    //清算阈值
+    pub liquidation_threshold: u64,
+    //清算奖励
+    pub liquidation_bonus: u64,
+    //该抵押资产可铸造的稳定币上限
+    pub debt_ceiling: u64,
+    //该抵押资产下所有仓位的本金化债务总量
+    pub total_normalized_debt: u64,
+    //所有仓位存入的该抵押资产总量
+    pub total_collateral: u64,
+    //是否允许开新仓和铸币，关闭后已有仓位仍可追加抵押、还款、赎回和清算
+    pub enabled: bool,
+    //熔断检查通过的最近一次价格(稳定币精度)
+    pub last_accepted_price: u64,
+    //最近一次接受价格的时间
+    pub last_accepted_price_time: i64,
+    //管理员覆盖价格的失效时间，在此之前预言机价格过期时必须传入覆盖价格账户，0表示没有覆盖价格
+    pub price_override_expires_at: i64,
+    pub self_bump: u8,
+    pub init_time: i64,
+    pub last_update_time: i64,
+}
+
+///备用预言机，价格来源可以与市场主预言机不同
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct SecondaryOracle {
+    //价格来源
+    pub source: OracleSource,
+    //价格账户所属程序，管理员签名价格不检查
+    pub oracle_program: Pubkey,
+    //价格账户地址，管理员签名价格为instructions sysvar
+    pub price_account: Pubkey,
+    //价格feed id，Switchboard为聚合器账户地址
+    pub feed_id: [u8; 32],
+}
+
+impl CollateralType {
+    pub fn validate_risk_parameters(&self) -> Result<()> {
+        require!(
+            self.max_ltv < self.liquidation_threshold
+                && self.liquidation_threshold <= crate::PERCENT_DENOMINATOR
+                && self.liquidation_bonus <= crate::MAX_LIQUIDATION_BONUS,
+            crate::ErrorCode::InvalidRiskParameters
+        );
+        Ok(())
+    }
+
+    pub fn validate_oracle_parameters(&self) -> Result<()> {
+        require!(self.max_age > 0, crate::ErrorCode::InvalidOracleParameters);
+        require!(
+            self.secondary_oracles.len() <= MAX_SECONDARY_ORACLES,
+            crate::ErrorCode::TooManyOracleAccounts
+        );
+        for (index, oracle) in self.secondary_oracles.iter().enumerate() {
+            require!(
+                self.secondary_oracles[..index]
+                    .iter()
+                    .all(|other| other.price_account != oracle.price_account),
+                crate::ErrorCode::DuplicateOracleAccount
+            );
+        }
+        Ok(())
+    }
+}
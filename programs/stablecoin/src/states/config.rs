@@ -1,10 +1,25 @@
 use anchor_lang::prelude::*;
 
+///市场使用的价格来源
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum OracleSource {
+    //Pyth pull预言机(PriceUpdateV2)
+    PythPull,
+    //Switchboard风格的聚合器账户
+    Switchboard,
+    //管理员ed25519签名的价格消息
+    SignedMessage,
+}
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct Config {
     //官方机构管理员
     pub authority: Pubkey,
+    //待接受的新管理员，没有提议时为默认公钥
+    pub pending_authority: Pubkey,
+    //各管理角色的持有人，由authority授予和撤销
+    pub roles: Roles,
     //铸币地址
     pub mint_account: Pubkey,
     //抵押物最大抵押率
@@ -15,8 +30,271 @@ pub struct Config {
     pub liquidation_bonus: u64,
     //最低健康因子
     pub min_health_factor: u64,
+    //上述风险参数修改从排队到可执行的等待时间(秒)
+    pub risk_param_timelock_secs: u64,
+    //年化稳定费率(bps)，按秒复利累计
+    pub stability_fee_bps: u64,
+    //债务累计利率指数，初始为DEBT_INDEX_PRECISION
+    pub debt_index: u128,
+    //上次累计利率的时间
+    pub last_accrual_time: i64,
+    //所有抵押资产类型的标准化债务之和，乘以debt_index为市场总债务
+    pub total_normalized_debt: u64,
+    //市场总债务上限
+    pub debt_ceiling: u64,
+    //已注册的抵押资产类型数量
+    pub collateral_type_count: u32,
+    //国库PDA，本身持有SOL，同时是国库稳定币账户和各抵押资产账户的authority
+    pub treasury_account: Pubkey,
+    //铸币手续费(bps)
+    pub mint_fee_bps: u64,
+    //赎回(还款)手续费(bps)
+    pub redeem_fee_bps: u64,
+    //清算奖励中归协议国库的比例(bps)
+    pub liquidation_protocol_share_bps: u64,
+    //清算系数(bps)，单次清算最多偿还仓位债务的比例
+    pub close_factor_bps: u64,
+    //预言机置信区间系数k(bps，10000表示k=1)，抵押资产按price - k*conf估值，债务按price + k*conf估值
+    pub oracle_conf_multiplier_bps: u64,
+    //允许的最大置信区间占价格比例(bps)，超过时拒绝该价格
+    pub max_oracle_conf_bps: u64,
+    //是否要求预言机价格经过完整验证(Full)，否则接受达到最少签名数的Partial验证
+    pub require_full_verification: bool,
+    //价格来源
+    pub oracle_source: OracleSource,
+    //预言机价格账户所属程序(Pyth receiver程序或Switchboard程序)
+    pub oracle_receiver_program: Pubkey,
+    //价格来源为管理员签名消息时的签名公钥
+    pub price_signer: Pubkey,
+    //主预言机与备用预言机(remaining_accounts)的价格汇总方式
+    pub oracle_aggregation: OracleAggregation,
+    //各预言机价格与选中价格允许的最大偏离(bps)
+    pub max_oracle_deviation_bps: u64,
+    //TWAP时间窗口(秒)
+    pub twap_window_secs: u64,
+    //铸币时计算抵押率和健康因子使用的价格
+    pub mint_price_mode: PriceMode,
+    //赎回、取回抵押资产时计算健康因子使用的价格
+    pub redeem_price_mode: PriceMode,
+    //清算时计算健康因子使用的价格
+    pub liquidation_price_mode: PriceMode,
+    //熔断阈值(bps)，新价格相对上次接受的价格偏离超过该值时暂停铸币和清算，0表示关闭
+    pub circuit_breaker_bps: u64,
+    //熔断比较的时间窗口(秒)，上次接受的价格早于窗口时不比较
+    pub circuit_breaker_window_secs: u64,
+    //熔断后暂停的时间(秒)
+    pub circuit_breaker_cooldown_secs: u64,
+    //熔断暂停截止时间，0表示未熔断
+    pub circuit_breaker_paused_until: i64,
+    //紧急暂停标志位，见PAUSE_DEPOSIT等常量
+    pub pause_flags: u8,
+    //管理员覆盖价格从设置到生效的等待时间(秒)
+    pub price_override_timelock_secs: u64,
+    //覆盖价格的最长有效期(秒)
+    pub max_price_override_duration_secs: u64,
     pub self_bump: u8,
     pub mint_account_bump: u8,
+    pub treasury_bump: u8,
     pub init_time: i64,
     pub last_update_time: i64,
 }
+
+///多个预言机价格的汇总方式
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum OracleAggregation {
+    //优先使用主预言机，主预言机不可用时使用备用预言机
+    Fallback,
+    //取所有可用价格的中位数
+    Median,
+}
+
+///计算健康因子时使用的价格
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum PriceMode {
+    //预言机即时价格
+    Spot,
+    //价格历史账户中的TWAP
+    Twap,
+    //即时价格和TWAP中较低者
+    MinOfSpotAndTwap,
+    //即时价格和TWAP中较高者
+    MaxOfSpotAndTwap,
+}
+
+///市场管理角色，authority负责授予和撤销
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    //风险参数(抵押率、清算参数、费率、熔断参数)
+    RiskAdmin,
+    //预言机参数和价格feed
+    OracleAdmin,
+    //紧急暂停
+    Pauser,
+    //国库提取
+    TreasuryAdmin,
+    //债务上限
+    MinterCapAdmin,
+}
+
+///各角色的持有人，未授予时为默认公钥
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Roles {
+    pub risk_admin: Pubkey,
+    pub oracle_admin: Pubkey,
+    pub pauser: Pubkey,
+    pub treasury_admin: Pubkey,
+    pub minter_cap_admin: Pubkey,
+}
+
+impl Roles {
+    ///初始化时所有角色都授予给authority
+    pub fn new(authority: Pubkey) -> Self {
+        Self {
+            risk_admin: authority,
+            oracle_admin: authority,
+            pauser: authority,
+            treasury_admin: authority,
+            minter_cap_admin: authority,
+        }
+    }
+
+    ///把old_holder持有的所有角色转给new_holder，管理员变更时调用
+    pub fn transfer_all(&mut self, old_holder: &Pubkey, new_holder: Pubkey) {
+        for role in [
+            Role::RiskAdmin,
+            Role::OracleAdmin,
+            Role::Pauser,
+            Role::TreasuryAdmin,
+            Role::MinterCapAdmin,
+        ] {
+            let holder = self.holder_mut(role);
+            if holder == old_holder {
+                *holder = new_holder;
+            }
+        }
+    }
+
+    pub fn holder_mut(&mut self, role: Role) -> &mut Pubkey {
+        match role {
+            Role::RiskAdmin => &mut self.risk_admin,
+            Role::OracleAdmin => &mut self.oracle_admin,
+            Role::Pauser => &mut self.pauser,
+            Role::TreasuryAdmin => &mut self.treasury_admin,
+            Role::MinterCapAdmin => &mut self.minter_cap_admin,
+        }
+    }
+
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        let holder = match role {
+            Role::RiskAdmin => self.risk_admin,
+            Role::OracleAdmin => self.oracle_admin,
+            Role::Pauser => self.pauser,
+            Role::TreasuryAdmin => self.treasury_admin,
+            Role::MinterCapAdmin => self.minter_cap_admin,
+        };
+        holder != Pubkey::default() && holder == *key
+    }
+
+    pub fn check_role(&self, role: Role, key: &Pubkey) -> Result<()> {
+        require!(self.has_role(role, key), crate::ErrorCode::Unauthorized);
+        Ok(())
+    }
+}
+
+impl Config {
+    ///对应操作被暂停时返回错误
+    pub fn check_not_paused(&self, flag: u8) -> Result<()> {
+        require!(
+            self.pause_flags & flag == 0,
+            crate::ErrorCode::OperationPaused
+        );
+        Ok(())
+    }
+
+    pub fn validate_risk_parameters(&self) -> Result<()> {
+        require!(
+            self.max_ltv < self.liquidation_threshold
+                && self.liquidation_threshold <= crate::PERCENT_DENOMINATOR
+                && self.liquidation_bonus <= crate::MAX_LIQUIDATION_BONUS
+                && self.min_health_factor >= crate::PERCENT_DENOMINATOR
+                && self.close_factor_bps > 0
+                && self.close_factor_bps <= crate::BPS_DENOMINATOR,
+            crate::ErrorCode::InvalidRiskParameters
+        );
+        Ok(())
+    }
+
+    pub fn validate_fee_parameters(&self) -> Result<()> {
+        require!(
+            self.mint_fee_bps <= crate::MAX_FEE_BPS
+                && self.redeem_fee_bps <= crate::MAX_FEE_BPS
+                && self.liquidation_protocol_share_bps <= crate::BPS_DENOMINATOR,
+            crate::ErrorCode::InvalidFeeParameters
+        );
+        Ok(())
+    }
+
+    pub fn validate_oracle_parameters(&self) -> Result<()> {
+        require!(
+            self.oracle_conf_multiplier_bps <= crate::MAX_ORACLE_CONF_MULTIPLIER_BPS
+                && self.max_oracle_conf_bps > 0
+                && self.max_oracle_conf_bps <= crate::BPS_DENOMINATOR
+                && self.max_oracle_deviation_bps > 0
+                && self.max_oracle_deviation_bps <= crate::BPS_DENOMINATOR
+                && self.twap_window_secs > 0
+                && self.circuit_breaker_bps <= crate::BPS_DENOMINATOR
+                && self.price_override_timelock_secs > 0
+                && self.max_price_override_duration_secs > 0,
+            crate::ErrorCode::InvalidOracleParameters
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_ROLES: [Role; 5] = [
+        Role::RiskAdmin,
+        Role::OracleAdmin,
+        Role::Pauser,
+        Role::TreasuryAdmin,
+        Role::MinterCapAdmin,
+    ];
+
+    #[test]
+    fn transfer_all_moves_only_old_holder_roles() {
+        let old_authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let pauser = Pubkey::new_unique();
+        let mut roles = Roles::new(old_authority);
+        roles.pauser = pauser;
+
+        roles.transfer_all(&old_authority, new_authority);
+
+        for role in ALL_ROLES {
+            assert!(!roles.has_role(role, &old_authority));
+        }
+        assert!(roles.has_role(Role::Pauser, &pauser));
+        for role in [
+            Role::RiskAdmin,
+            Role::OracleAdmin,
+            Role::TreasuryAdmin,
+            Role::MinterCapAdmin,
+        ] {
+            assert!(roles.has_role(role, &new_authority));
+        }
+    }
+
+    #[test]
+    fn renounced_roles_reject_everyone() {
+        let authority = Pubkey::new_unique();
+        let roles = Roles::new(Pubkey::default());
+
+        for role in ALL_ROLES {
+            assert!(!roles.has_role(role, &authority));
+            assert!(!roles.has_role(role, &Pubkey::default()));
+        }
+    }
+}
@@ -1,5 +1,15 @@
 mod collateral;
+mod collateral_type;
 mod config;
+mod price_history;
+mod price_override;
+mod pending_config_change;
+mod multisig;
 
 pub use collateral::*;
-pub use config::*;
\ No newline at end of file
+pub use collateral_type::*;
+pub use config::*;
+pub use price_history::*;
+pub use price_override::*;
+pub use pending_config_change::*;
+pub use multisig::*;
@@ -0,0 +1,116 @@
+use crate::constants::{
+    MAX_MULTISIG_OWNERS, MAX_MULTISIG_TRANSACTION_ACCOUNTS, MAX_MULTISIG_TRANSACTION_DATA,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+///M-of-N多签，签名PDA可以设置为市场的authority或各管理角色
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Multisig {
+    //创建人和编号，用于推导多签账户地址
+    pub creator: Pubkey,
+    pub multisig_id: u64,
+    //成员
+    #[max_len(MAX_MULTISIG_OWNERS)]
+    pub owners: Vec<Pubkey>,
+    //执行交易所需的最少批准数
+    pub threshold: u8,
+    //成员变更次数，成员变更后之前提议的交易失效
+    pub owner_set_seqno: u32,
+    //已提议的交易数量，作为交易账户地址的序号
+    pub transaction_count: u64,
+    pub self_bump: u8,
+    //签名PDA的bump，签名PDA是不存数据的系统账户，可以支付租金
+    pub signer_bump: u8,
+}
+
+impl Multisig {
+    pub fn owner_index(&self, key: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|owner| owner == key)
+    }
+
+    pub fn validate_owners(&self) -> Result<()> {
+        let has_duplicate = self
+            .owners
+            .iter()
+            .enumerate()
+            .any(|(i, owner)| self.owners[..i].contains(owner));
+        require!(
+            !self.owners.is_empty() && self.owners.len() <= MAX_MULTISIG_OWNERS && !has_duplicate,
+            crate::ErrorCode::InvalidMultisigOwners
+        );
+        require!(
+            self.threshold > 0 && self.threshold as usize <= self.owners.len(),
+            crate::ErrorCode::InvalidMultisigThreshold
+        );
+        Ok(())
+    }
+}
+
+///多签交易中引用的账户
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<&TransactionAccount> for AccountMeta {
+    fn from(account: &TransactionAccount) -> Self {
+        AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }
+    }
+}
+
+///成员提议的待执行指令，批准数达到阈值后由签名PDA签名执行
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MultisigTransaction {
+    //所属多签
+    pub multisig: Pubkey,
+    pub proposer: Pubkey,
+    //要调用的程序、账户和指令数据
+    pub program_id: Pubkey,
+    #[max_len(MAX_MULTISIG_TRANSACTION_ACCOUNTS)]
+    pub accounts: Vec<TransactionAccount>,
+    #[max_len(MAX_MULTISIG_TRANSACTION_DATA)]
+    pub data: Vec<u8>,
+    //与owners一一对应，true表示该成员已批准
+    #[max_len(MAX_MULTISIG_OWNERS)]
+    pub signers: Vec<bool>,
+    //提议时的成员变更次数
+    pub owner_set_seqno: u32,
+    pub executed: bool,
+    pub self_bump: u8,
+    pub init_time: i64,
+}
+
+impl MultisigTransaction {
+    pub fn approvals(&self) -> usize {
+        self.signers.iter().filter(|approved| **approved).count()
+    }
+
+    ///构造要执行的指令，签名PDA在指令中标记为签名人
+    pub fn to_instruction(&self, multisig_signer: &Pubkey) -> Instruction {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| {
+                let mut meta = AccountMeta::from(account);
+                if meta.pubkey == *multisig_signer {
+                    meta.is_signer = true;
+                }
+                meta
+            })
+            .collect();
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: self.data.clone(),
+        }
+    }
+}
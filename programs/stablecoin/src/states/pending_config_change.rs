@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+///排队中的风险参数修改，到达eta后才能执行，市场和每个抵押资产类型同时各只有一个
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PendingConfigChange {
+    //所属市场配置账户
+    pub config_account: Pubkey,
+    //提议人，执行或取消后租金退回该账户
+    pub proposer: Pubkey,
+    //修改的抵押资产类型，默认公钥表示修改市场级参数
+    pub collateral_type: Pubkey,
+    //提议的新值，None表示不修改
+    pub max_ltv: Option<u64>,
+    pub liquidation_threshold: Option<u64>,
+    pub liquidation_bonus: Option<u64>,
+    pub min_health_factor: Option<u64>,
+    pub risk_param_timelock_secs: Option<u64>,
+    //排队时间
+    pub queued_at: i64,
+    //最早可执行时间
+    pub eta: i64,
+    pub self_bump: u8,
+}
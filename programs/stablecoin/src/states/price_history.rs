@@ -0,0 +1,166 @@
+use crate::constants::PRICE_HISTORY_CAPACITY;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct PriceObservation {
+    //记录时间
+    pub timestamp: i64,
+    //换算到稳定币精度后的预言机报价
+    pub price: u64,
+}
+
+///抵押资产价格历史，环形缓冲区保存最近的价格观测，由poke_price指令更新
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PriceHistory {
+    //所属抵押资产类型
+    pub collateral_type: Pubkey,
+    pub observations: [PriceObservation; PRICE_HISTORY_CAPACITY],
+    //下一次写入的位置
+    pub head: u16,
+    //已保存的观测数量
+    pub count: u16,
+    pub self_bump: u8,
+    pub init_time: i64,
+    pub last_update_time: i64,
+}
+
+impl PriceHistory {
+    ///最近一次观测
+    pub fn latest(&self) -> Option<PriceObservation> {
+        if self.count == 0 {
+            return None;
+        }
+        let index = (self.head as usize + PRICE_HISTORY_CAPACITY - 1) % PRICE_HISTORY_CAPACITY;
+        Some(self.observations[index])
+    }
+
+    ///两次观测之间的最小间隔(秒)，保证写满的环形缓冲区至少覆盖一个TWAP窗口
+    pub fn min_observation_interval(twap_window_secs: u64) -> i64 {
+        let interval = twap_window_secs.div_ceil(PRICE_HISTORY_CAPACITY as u64 - 1);
+        i64::try_from(interval.max(1)).unwrap_or(i64::MAX)
+    }
+
+    ///写入一次观测，距上次观测不足min_interval秒时不记录
+    pub fn push(&mut self, timestamp: i64, price: u64, min_interval: i64) -> bool {
+        if let Some(latest) = self.latest() {
+            if timestamp < latest.timestamp.saturating_add(min_interval) {
+                return false;
+            }
+        }
+        self.observations[self.head as usize] = PriceObservation { timestamp, price };
+        self.head = ((self.head as usize + 1) % PRICE_HISTORY_CAPACITY) as u16;
+        if (self.count as usize) < PRICE_HISTORY_CAPACITY {
+            self.count += 1;
+        }
+        true
+    }
+
+    ///计算最近window_secs秒内的时间加权平均价格，每个观测的价格一直有效到下一个观测
+    ///最近一次观测早于窗口起点，或最早的观测晚于窗口起点(历史不足一个窗口)时返回None
+    pub fn calc_twap(&self, now: i64, window_secs: u64) -> Option<u64> {
+        let latest = self.latest()?;
+        let window_start = now.checked_sub(i64::try_from(window_secs).ok()?)?;
+        if latest.timestamp < window_start {
+            return None;
+        }
+        let start = (self.head as usize + PRICE_HISTORY_CAPACITY - self.count as usize)
+            % PRICE_HISTORY_CAPACITY;
+        if self.observations[start].timestamp > window_start {
+            return None;
+        }
+        let mut weighted_sum: u128 = 0;
+        let mut total_weight: u128 = 0;
+        for i in 0..self.count as usize {
+            let observation = self.observations[(start + i) % PRICE_HISTORY_CAPACITY];
+            let end = if i + 1 < self.count as usize {
+                self.observations[(start + i + 1) % PRICE_HISTORY_CAPACITY].timestamp
+            } else {
+                now
+            };
+            let begin = observation.timestamp.max(window_start);
+            if end <= begin {
+                continue;
+            }
+            let weight = (end - begin) as u128;
+            weighted_sum =
+                weighted_sum.checked_add((observation.price as u128).checked_mul(weight)?)?;
+            total_weight += weight;
+        }
+        if total_weight == 0 {
+            return Some(latest.price);
+        }
+        u64::try_from(weighted_sum / total_weight).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_history() -> PriceHistory {
+        PriceHistory {
+            collateral_type: Pubkey::default(),
+            observations: [PriceObservation::default(); PRICE_HISTORY_CAPACITY],
+            head: 0,
+            count: 0,
+            self_bump: 0,
+            init_time: 0,
+            last_update_time: 0,
+        }
+    }
+
+    #[test]
+    fn push_enforces_min_interval() {
+        let min_interval = PriceHistory::min_observation_interval(1_800);
+        assert_eq!(min_interval, 59);
+        let mut history = empty_history();
+        assert!(history.push(1_000, 100, min_interval));
+        assert!(!history.push(1_000 + min_interval - 1, 200, min_interval));
+        assert!(history.push(1_000 + min_interval, 200, min_interval));
+        assert_eq!(history.count, 2);
+        assert_eq!(PriceHistory::min_observation_interval(0), 1);
+    }
+
+    #[test]
+    fn full_history_at_min_interval_covers_window() {
+        let window_secs = 1_800;
+        let min_interval = PriceHistory::min_observation_interval(window_secs);
+        let mut history = empty_history();
+        let mut now = 0;
+        for _ in 0..PRICE_HISTORY_CAPACITY * 2 {
+            now += min_interval;
+            assert!(history.push(now, 100, min_interval));
+            //第一个观测之后满一个窗口才有TWAP，之后缓冲区写满被覆盖也始终可用
+            assert_eq!(
+                history.calc_twap(now, window_secs).is_some(),
+                now >= min_interval + window_secs as i64
+            );
+        }
+    }
+
+    #[test]
+    fn twap_unavailable_when_history_shorter_than_window() {
+        let mut history = empty_history();
+        //在窗口内快速写入的即时价格不能代替整个窗口的TWAP
+        for i in 0..PRICE_HISTORY_CAPACITY as i64 {
+            history.push(10_000 + i, 500, 1);
+        }
+        assert_eq!(history.calc_twap(10_040, 1_800), None);
+        //最近一次观测早于窗口起点
+        assert_eq!(history.calc_twap(20_000, 1_800), None);
+    }
+
+    #[test]
+    fn twap_weights_prices_by_time_in_window() {
+        let mut history = empty_history();
+        history.push(0, 100, 1);
+        history.push(1_000, 200, 1);
+        history.push(1_500, 400, 1);
+        //窗口[200, 2000]: 100持续800秒，200持续500秒，400持续500秒
+        assert_eq!(
+            history.calc_twap(2_000, 1_800),
+            Some((100 * 800 + 200 * 500 + 400 * 500) / 1_800)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+///管理员设置的紧急覆盖价格，生效期间在预言机价格过期时代替预言机价格
+///设置后需要经过Config中的timelock才生效，并且只在有限时间内有效
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PriceOverride {
+    //所属市场配置账户
+    pub config_account: Pubkey,
+    //所属抵押资产类型
+    pub collateral_type: Pubkey,
+    //覆盖价格，真实价格为 price * 10^exponent
+    pub price: i64,
+    //置信区间，与price同精度
+    pub conf: u64,
+    pub exponent: i32,
+    //设置时间
+    pub set_time: i64,
+    //生效时间
+    pub effective_at: i64,
+    //失效时间，清除覆盖价格时置为0
+    pub expires_at: i64,
+    pub self_bump: u8,
+}
+
+impl PriceOverride {
+    pub fn is_active(&self, now: i64) -> bool {
+        self.effective_at <= now && now < self.expires_at
+    }
+}
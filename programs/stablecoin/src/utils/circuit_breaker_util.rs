@@ -0,0 +1,186 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::events::CircuitBreakerTriggered;
+use crate::states::{CollateralType, Config};
+use crate::utils::{mul_div_u64, Rounding};
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitBreakerStatus {
+    //价格正常，可以接受
+    Normal,
+    //熔断中，等待冷却时间结束或管理员resume
+    Paused,
+    //新价格相对上次接受的价格偏离过大，应当触发熔断
+    Tripped,
+}
+
+///判断新价格是否触发熔断
+///只有上次接受的价格在时间窗口内时才比较偏离，窗口外的旧价格不作为参考
+pub fn evaluate_circuit_breaker(
+    config_account: &Config,
+    collateral_type: &CollateralType,
+    price: u64,
+    now: i64,
+) -> Option<CircuitBreakerStatus> {
+    if now < config_account.circuit_breaker_paused_until {
+        return Some(CircuitBreakerStatus::Paused);
+    }
+    let reference_price = collateral_type.last_accepted_price;
+    let reference_age = now.saturating_sub(collateral_type.last_accepted_price_time);
+    if config_account.circuit_breaker_bps == 0
+        || reference_price == 0
+        || reference_age > config_account.circuit_breaker_window_secs as i64
+    {
+        return Some(CircuitBreakerStatus::Normal);
+    }
+    let max_deviation = mul_div_u64(
+        reference_price,
+        config_account.circuit_breaker_bps,
+        BPS_DENOMINATOR,
+        Rounding::Down,
+    )?;
+    if price.abs_diff(reference_price) > max_deviation {
+        msg!(
+            "circuit breaker tripped, last accepted price:{}, new price:{}",
+            reference_price,
+            price
+        );
+        return Some(CircuitBreakerStatus::Tripped);
+    }
+    Some(CircuitBreakerStatus::Normal)
+}
+
+///触发熔断：记录暂停截止时间并清除参考价格，冷却结束后第一个价格作为新的参考价格
+pub fn trip_circuit_breaker(
+    config_account: &mut Config,
+    collateral_type: &mut CollateralType,
+    collateral_type_key: Pubkey,
+    price: u64,
+    now: i64,
+) -> Result<()> {
+    config_account.circuit_breaker_paused_until = now
+        .checked_add(config_account.circuit_breaker_cooldown_secs as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    emit!(CircuitBreakerTriggered {
+        config_account: collateral_type.config_account,
+        collateral_type: collateral_type_key,
+        reference_price: collateral_type.last_accepted_price,
+        price,
+        paused_until: config_account.circuit_breaker_paused_until,
+    });
+    collateral_type.last_accepted_price = 0;
+    collateral_type.last_accepted_price_time = 0;
+    msg!(
+        "circuit breaker paused until:{}",
+        config_account.circuit_breaker_paused_until
+    );
+    Ok(())
+}
+
+///铸币和清算前检查熔断，价格正常时记录为上次接受的价格
+///熔断中或本次价格触发熔断时返回错误，熔断状态只由poke_price保存
+pub fn check_circuit_breaker(
+    config_account: &Config,
+    collateral_type: &mut CollateralType,
+    price: u64,
+    now: i64,
+) -> Result<()> {
+    match evaluate_circuit_breaker(config_account, collateral_type, price, now)
+        .ok_or(ErrorCode::MathOverflow)?
+    {
+        CircuitBreakerStatus::Normal => {
+            collateral_type.last_accepted_price = price;
+            collateral_type.last_accepted_price_time = now;
+            Ok(())
+        }
+        CircuitBreakerStatus::Paused | CircuitBreakerStatus::Tripped => {
+            Err(ErrorCode::CircuitBreakerTripped.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::{assert_error, test_collateral_type, test_config};
+
+    fn breaker_config() -> Config {
+        let mut config = test_config();
+        //10分钟内偏离超过20%时熔断，暂停1小时
+        config.circuit_breaker_bps = 2_000;
+        config.circuit_breaker_window_secs = 600;
+        config.circuit_breaker_cooldown_secs = 3_600;
+        config
+    }
+
+    #[test]
+    fn normal_price_is_accepted_as_reference() {
+        let mut config = breaker_config();
+        let mut collateral_type = test_collateral_type();
+        check_circuit_breaker(&config, &mut collateral_type, 100, 1_000).unwrap();
+        check_circuit_breaker(&config, &mut collateral_type, 110, 1_100).unwrap();
+        assert_eq!(collateral_type.last_accepted_price, 110);
+        assert_eq!(collateral_type.last_accepted_price_time, 1_100);
+        assert_eq!(config.circuit_breaker_paused_until, 0);
+        config.circuit_breaker_bps = 0;
+        check_circuit_breaker(&config, &mut collateral_type, 1_000, 1_200).unwrap();
+    }
+
+    #[test]
+    fn tripping_price_is_rejected_without_accepting_it() {
+        let config = breaker_config();
+        let mut collateral_type = test_collateral_type();
+        check_circuit_breaker(&config, &mut collateral_type, 100, 1_000).unwrap();
+
+        //价格跳涨50%：拒绝本次操作，参考价格保持不变
+        assert_error(
+            check_circuit_breaker(&config, &mut collateral_type, 150, 1_100),
+            ErrorCode::CircuitBreakerTripped,
+        );
+        assert_eq!(collateral_type.last_accepted_price, 100);
+        assert_eq!(collateral_type.last_accepted_price_time, 1_000);
+    }
+
+    #[test]
+    fn trip_is_persisted_and_later_mint_is_rejected() {
+        let mut config = breaker_config();
+        let mut collateral_type = test_collateral_type();
+        check_circuit_breaker(&config, &mut collateral_type, 100, 1_000).unwrap();
+
+        //poke_price遇到跳涨50%的价格时保存熔断状态
+        trip_circuit_breaker(
+            &mut config,
+            &mut collateral_type,
+            Pubkey::new_unique(),
+            150,
+            1_100,
+        )
+        .unwrap();
+        assert_eq!(config.circuit_breaker_paused_until, 4_700);
+        assert_eq!(collateral_type.last_accepted_price, 0);
+
+        //冷却期内之后的铸币被拒绝，即使价格已恢复
+        assert_error(
+            check_circuit_breaker(&config, &mut collateral_type, 100, 1_200),
+            ErrorCode::CircuitBreakerTripped,
+        );
+        assert_error(
+            check_circuit_breaker(&config, &mut collateral_type, 100, 4_699),
+            ErrorCode::CircuitBreakerTripped,
+        );
+
+        //冷却结束后第一个价格作为新的参考价格
+        check_circuit_breaker(&config, &mut collateral_type, 150, 4_700).unwrap();
+        assert_eq!(collateral_type.last_accepted_price, 150);
+    }
+
+    #[test]
+    fn reference_price_outside_window_is_ignored() {
+        let config = breaker_config();
+        let mut collateral_type = test_collateral_type();
+        check_circuit_breaker(&config, &mut collateral_type, 100, 1_000).unwrap();
+        check_circuit_breaker(&config, &mut collateral_type, 150, 1_601).unwrap();
+        assert_eq!(config.circuit_breaker_paused_until, 0);
+    }
+}
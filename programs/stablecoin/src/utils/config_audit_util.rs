@@ -0,0 +1,38 @@
+use crate::events::{ConfigUpdated, ConfigValue};
+use crate::states::{Role, Roles};
+use anchor_lang::prelude::*;
+
+///修改配置时记录事件所需的信息
+pub struct ConfigAudit {
+    pub config_account: Pubkey,
+    pub collateral_type: Pubkey,
+    pub admin: Pubkey,
+    pub roles: Roles,
+    pub update_time: i64,
+}
+
+///检查签名人持有该配置项对应的角色，修改配置项并发出记录修改前后值的事件
+pub fn set_config_value<T>(
+    slot: &mut T,
+    value: T,
+    field: &str,
+    role: Role,
+    audit: &ConfigAudit,
+) -> Result<()>
+where
+    T: Copy + Into<ConfigValue>,
+{
+    audit.roles.check_role(role, &audit.admin)?;
+    emit!(ConfigUpdated {
+        config_account: audit.config_account,
+        collateral_type: audit.collateral_type,
+        admin: audit.admin,
+        field: field.to_string(),
+        old_value: (*slot).into(),
+        new_value: value.into(),
+        update_time: audit.update_time,
+    });
+    msg!("update config {}", field);
+    *slot = value;
+    Ok(())
+}
@@ -1,69 +1,95 @@
-use crate::constants::{MAX_AGE, SOL_USD_FEED_ID};
 use crate::errors::ErrorCode;
+use crate::utils::{apply_percent, calc_health_factor_wad, mul_div_u64, percent_to_wad, Rounding};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
-use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::token_2022::{mint_to, MintTo};
-use num_traits::cast::FromPrimitive;
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
 
-pub fn get_collateral_in_usd(price_update: &PriceUpdateV2) -> Result<u64> {
-    let feed_id = get_feed_id_from_hex(SOL_USD_FEED_ID)?;
-    let price = price_update.get_price_no_older_than(&Clock::get()?, MAX_AGE, &feed_id)?;
+///抵押资产一个完整单位(10^decimals个最小单位)
+pub fn one_collateral_unit(collateral_decimals: u8) -> Option<u64> {
+    10u64.checked_pow(collateral_decimals as u32)
+}
 
-    Ok(price.price as u64)
+///抵押资产价值 = 数量 * 单价 / 一个完整单位，向下取整
+///先乘后除，按最小单位(如lamports)计价，不足一个完整单位的抵押资产同样计入价值
+///单价为get_collateral_in_usd换算后的价格，结果以稳定币最小单位表示
+pub fn calc_collateral_value(
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
+    collateral_amount: u64,
+) -> Option<u64> {
+    mul_div_u64(
+        collateral_amount,
+        collateral_in_usd,
+        one_collateral_unit(collateral_decimals)?,
+        Rounding::Down,
+    )
+}
+
+///给定价值对应的抵押资产数量，向下取整，保证转出的抵押资产不超过对应价值
+pub fn calc_collateral_amount_for_value(
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
+    value: u64,
+) -> Option<u64> {
+    mul_div_u64(
+        value,
+        one_collateral_unit(collateral_decimals)?,
+        collateral_in_usd,
+        Rounding::Down,
+    )
 }
 
 pub fn calc_mintable_amount(
     deposit_collateral_amount: u64,
     collateral_in_usd: u64,
+    collateral_decimals: u8,
     max_ltv: u64,
 ) -> Option<u64> {
     let collateral_value =
-        collateral_in_usd.checked_mul(deposit_collateral_amount / LAMPORTS_PER_SOL)?;
+        calc_collateral_value(collateral_in_usd, collateral_decimals, deposit_collateral_amount)?;
     msg!("collateral_value:{}", collateral_value);
     if collateral_value == 0 {
         return None;
     }
 
-    let value = collateral_value as f64 * max_ltv as f64 / 100.0;
-
-    u64::from_f64(value)
+    apply_percent(collateral_value, max_ltv, Rounding::Down)
 }
 
 pub fn calc_redeemable_amount(
-    amount_to_burn:u64,
-    collateral_in_usd: u64
+    amount_to_burn: u64,
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
 ) -> Option<u64> {
-    amount_to_burn.checked_div(collateral_in_usd)?.checked_mul(LAMPORTS_PER_SOL)
+    calc_collateral_amount_for_value(collateral_in_usd, collateral_decimals, amount_to_burn)
 }
 
+///返回WAD精度的健康因子
 pub fn calc_health_factor_when_deposit_collateral_and_mint_new_tokens(
-    price_update: &PriceUpdateV2,
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
     amount_deposited: u64,
     amount_minted: u64,
     amount_to_deposit: u64,
     amount_to_mint: u64,
     liquidation_threshold: u64,
-) -> Option<f64> {
-    let collateral_in_usd =
-        get_collateral_in_usd(price_update).expect("invoke get_collateral_in_usd encounter error");
-    let collateral_total_value = collateral_in_usd.checked_mul(
-        amount_deposited
-            .checked_add(amount_to_deposit)?
-            .checked_div(LAMPORTS_PER_SOL)?,
+) -> Option<u128> {
+    let collateral_total_value = calc_collateral_value(
+        collateral_in_usd,
+        collateral_decimals,
+        amount_deposited.checked_add(amount_to_deposit)?,
     )?;
-    let health_factor = collateral_total_value as f64 * (liquidation_threshold as f64 / 100.0)
-        / (amount_minted as f64 + amount_to_mint as f64);
 
-    let rounded = round_to_n_decimals(health_factor, 4);
-    Some(rounded)
+    calc_health_factor_wad(
+        collateral_total_value,
+        amount_minted.checked_add(amount_to_mint)?,
+        liquidation_threshold,
+    )
 }
 
-
-
+#[allow(clippy::too_many_arguments)]
 pub fn check_health_factor_when_deposit_collateral_and_mint_new_tokens(
-    price_update: &PriceUpdateV2,
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
     amount_deposited: u64,
     amount_minted: u64,
     amount_to_deposit: u64,
@@ -72,36 +98,42 @@ pub fn check_health_factor_when_deposit_collateral_and_mint_new_tokens(
     configured_min_health_factor:u64
 ) -> Result<()> {
     let health_factor = calc_health_factor_when_deposit_collateral_and_mint_new_tokens(
-        price_update,
+        collateral_in_usd,
+        collateral_decimals,
         amount_deposited,
         amount_minted,
         amount_to_deposit,
         amount_to_mint,
         liquidation_threshold,
     )
-    .expect("invoke method check_health_factor encounter error!");
+    .ok_or(ErrorCode::MathOverflow)?;
     msg!("health factor after try to deposit and mint:{}", health_factor);
-    if health_factor < configured_min_health_factor as f64 / 100.0 {
+    let min_health_factor =
+        percent_to_wad(configured_min_health_factor).ok_or(ErrorCode::MathOverflow)?;
+    if health_factor < min_health_factor {
         return Err(ErrorCode::HealthFactorLessThanOne.into());
     }
 
     Ok(())
 }
 
-pub fn round_to_n_decimals(original_num: f64, n_decimals: i32) -> f64 {
-    let base = 10.0f64.powi(n_decimals);
-    (original_num * base).round() / base
-}
-
 pub fn deposit_collateral<'info>(
     amount_to_deposit: u64,
+    collateral_decimals: u8,
+    mint: AccountInfo<'info>,
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
     program: AccountInfo<'info>,
 ) -> Result<()> {
-    let accounts = Transfer { from, to };
+    let accounts = TransferChecked {
+        from,
+        mint,
+        to,
+        authority,
+    };
     let cpi_ctx = CpiContext::new(program, accounts);
-    transfer(cpi_ctx, amount_to_deposit)
+    transfer_checked(cpi_ctx, amount_to_deposit, collateral_decimals)
 }
 
 pub fn mint_stable_coins<'info>(
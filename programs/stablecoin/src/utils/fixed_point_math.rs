@@ -0,0 +1,140 @@
+use crate::constants::{PERCENT_DENOMINATOR, WAD};
+
+///取整方向，所有风险计算都按对协议有利的方向取整
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+///计算 a * b / denominator，中间结果使用u128避免溢出
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+    let product = a.checked_mul(b)?;
+    let quotient = product / denominator;
+    match rounding {
+        Rounding::Up if product % denominator != 0 => quotient.checked_add(1),
+        _ => Some(quotient),
+    }
+}
+
+///u64版本的mul_div，结果超出u64范围时返回None
+pub fn mul_div_u64(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    let result = mul_div(a as u128, b as u128, denominator as u128, rounding)?;
+    u64::try_from(result).ok()
+}
+
+///按百分比取值，例如 amount * max_ltv / 100
+pub fn apply_percent(amount: u64, percent: u64, rounding: Rounding) -> Option<u64> {
+    mul_div_u64(amount, percent, PERCENT_DENOMINATOR, rounding)
+}
+
+///WAD精度的幂运算 base^exponent，按平方求幂，每次乘法向下取整
+pub fn rpow_wad(base: u128, exponent: u64) -> Option<u128> {
+    let mut result = WAD;
+    let mut base = base;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_div(result, base, WAD, Rounding::Down)?;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = mul_div(base, base, WAD, Rounding::Down)?;
+        }
+    }
+    Some(result)
+}
+
+///把以百分比配置的健康因子(100 = 1.0)转换为WAD精度
+pub fn percent_to_wad(percent: u64) -> Option<u128> {
+    mul_div(
+        percent as u128,
+        WAD,
+        PERCENT_DENOMINATOR as u128,
+        Rounding::Down,
+    )
+}
+
+///健康因子 = 抵押价值 * 清算阈值 / 债务，WAD精度，向下取整
+///没有债务时健康因子视为无穷大
+pub fn calc_health_factor_wad(
+    collateral_value: u64,
+    debt: u64,
+    liquidation_threshold: u64,
+) -> Option<u128> {
+    if debt == 0 {
+        return Some(u128::MAX);
+    }
+    let adjusted_collateral_value = mul_div(
+        collateral_value as u128,
+        liquidation_threshold as u128,
+        PERCENT_DENOMINATOR as u128,
+        Rounding::Down,
+    )?;
+    mul_div(adjusted_collateral_value, WAD, debt as u128, Rounding::Down)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_rounds_in_requested_direction() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down), Some(3));
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up), Some(4));
+        //整除时两种取整结果相同
+        assert_eq!(mul_div(9, 1, 3, Rounding::Down), Some(3));
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up), Some(3));
+        assert_eq!(mul_div_u64(7, 3, 2, Rounding::Down), Some(10));
+        assert_eq!(mul_div_u64(7, 3, 2, Rounding::Up), Some(11));
+    }
+
+    #[test]
+    fn rpow_wad_matches_repeated_multiplication() {
+        assert_eq!(rpow_wad(3 * WAD, 0), Some(WAD));
+        assert_eq!(rpow_wad(3 * WAD, 1), Some(3 * WAD));
+        assert_eq!(rpow_wad(3 * WAD, 5), Some(243 * WAD));
+        assert_eq!(rpow_wad(WAD / 2, 3), Some(WAD / 8));
+        assert_eq!(rpow_wad(WAD, u64::MAX), Some(WAD));
+        //结果超出u128
+        assert_eq!(rpow_wad(1_000 * WAD, 10), None);
+    }
+
+    #[test]
+    fn mul_div_returns_none_on_zero_denominator() {
+        assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+        assert_eq!(mul_div(1, 1, 0, Rounding::Up), None);
+        assert_eq!(mul_div_u64(1, 1, 0, Rounding::Down), None);
+    }
+
+    #[test]
+    fn mul_div_returns_none_on_overflow() {
+        //乘积超出u128
+        assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Down), None);
+        assert_eq!(mul_div(u128::MAX, 2, 3, Rounding::Up), None);
+        //结果超出u64
+        assert_eq!(mul_div_u64(u64::MAX, 2, 1, Rounding::Down), None);
+        //中间乘积超出u64但结果在范围内
+        assert_eq!(
+            mul_div_u64(u64::MAX, u64::MAX, u64::MAX, Rounding::Down),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn apply_percent_rounds_in_requested_direction() {
+        assert_eq!(apply_percent(101, 50, Rounding::Down), Some(50));
+        assert_eq!(apply_percent(101, 50, Rounding::Up), Some(51));
+        assert_eq!(apply_percent(u64::MAX, 200, Rounding::Down), None);
+    }
+
+    #[test]
+    fn percent_to_wad_scales_percent() {
+        assert_eq!(percent_to_wad(100), Some(WAD));
+        assert_eq!(percent_to_wad(150), Some(WAD * 3 / 2));
+        assert_eq!(percent_to_wad(0), Some(0));
+    }
+}
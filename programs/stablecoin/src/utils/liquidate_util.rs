@@ -1,31 +1,40 @@
-use crate::{get_collateral_in_usd, round_to_n_decimals};
-use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use crate::{
+    apply_percent, calc_collateral_amount_for_value, calc_collateral_value,
+    calc_health_factor_wad, mul_div_u64, Rounding, BPS_DENOMINATOR,
+};
 
+///返回WAD精度的健康因子
 pub fn calc_health_factor_when_liquidate(
-    price_update: &PriceUpdateV2,
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
     collateral_total_amount: u64,
     stablecoin_total_minted: u64,
     liquidation_threshold: u64,
-) -> Option<f64> {
-    let collateral_in_usd =
-        get_collateral_in_usd(price_update).expect("invoke get_collateral_in_usd encounter error");
+) -> Option<u128> {
     let collateral_total_value =
-        collateral_in_usd.checked_mul(collateral_total_amount.checked_div(LAMPORTS_PER_SOL)?)?;
-    let health_factor = collateral_total_value as f64 * (liquidation_threshold as f64 / 100.0)
-        / stablecoin_total_minted as f64;
+        calc_collateral_value(collateral_in_usd, collateral_decimals, collateral_total_amount)?;
 
-    let rounded = round_to_n_decimals(health_factor, 4);
-    Some(rounded)
+    calc_health_factor_wad(
+        collateral_total_value,
+        stablecoin_total_minted,
+        liquidation_threshold,
+    )
 }
 
 pub fn calc_liquidatable_collateral(
-    price_update: &PriceUpdateV2,
+    collateral_in_usd: u64,
+    collateral_decimals: u8,
     amount_to_burn: u64,
 ) -> Option<u64> {
-    let collateral_in_usd =
-        get_collateral_in_usd(price_update).expect("invoke get_collateral_in_usd encounter error");
-    let liquidatable_amount = amount_to_burn.checked_div(collateral_in_usd)?.checked_mul(LAMPORTS_PER_SOL)?;    
-    
-    Some(liquidatable_amount)
+    calc_collateral_amount_for_value(collateral_in_usd, collateral_decimals, amount_to_burn)
+}
+
+///清算奖励 = 清算出的抵押资产 * 清算奖励百分比，向下取整
+pub fn calc_liquidation_bonus(liquidatable_amount: u64, liquidation_bonus: u64) -> Option<u64> {
+    apply_percent(liquidatable_amount, liquidation_bonus, Rounding::Down)
+}
+
+///单次清算最多可偿还的债务 = 仓位债务 * 清算系数，向下取整
+pub fn calc_max_liquidation_repay(current_debt: u64, close_factor_bps: u64) -> Option<u64> {
+    mul_div_u64(current_debt, close_factor_bps, BPS_DENOMINATOR, Rounding::Down)
 }
@@ -1,7 +1,31 @@
+mod fixed_point_math;
+mod price_source;
+mod pyth_price_source;
+mod switchboard_price_source;
+mod signed_price_source;
+mod oracle_aggregation;
+mod price_util;
+mod circuit_breaker_util;
 mod deposit_and_mint_util;
 mod burn_and_redeem_util;
 mod liquidate_util;
+mod stability_fee_util;
+mod treasury_util;
+mod config_audit_util;
+#[cfg(test)]
+mod test_util;
 
+pub use fixed_point_math::*;
+pub use price_source::*;
+pub use pyth_price_source::*;
+pub use switchboard_price_source::*;
+pub use signed_price_source::*;
+pub use oracle_aggregation::*;
+pub use price_util::*;
+pub use circuit_breaker_util::*;
 pub use deposit_and_mint_util::*;
 pub use burn_and_redeem_util::*;
-pub use liquidate_util::*;
\ No newline at end of file
+pub use liquidate_util::*;
+pub use stability_fee_util::*;
+pub use treasury_util::*;
+pub use config_audit_util::*;
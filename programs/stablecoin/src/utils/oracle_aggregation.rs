@@ -0,0 +1,197 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config, OracleAggregation};
+use crate::utils::{mul_div_u64, normalize_price, OraclePrice, PriceSource, Rounding};
+use anchor_lang::prelude::*;
+
+///汇总主预言机和备用预言机的价格
+///Fallback: 主预言机可用时取主预言机价格，否则取第一个可用的备用预言机价格
+///Median: 取所有可用价格的中位数(偶数个时取偏低的一个)
+///任一可用价格与选中价格的偏离超过max_oracle_deviation_bps时拒绝
+pub fn aggregate_oracle_prices(
+    price_sources: &[Box<dyn PriceSource>],
+    collateral_type: &CollateralType,
+    config_account: &Config,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    let mut primary_error = None;
+    //(换算到稳定币精度后的价格, 原始价格)
+    let mut prices: Vec<(u64, OraclePrice)> = Vec::with_capacity(price_sources.len());
+    for (index, price_source) in price_sources.iter().enumerate() {
+        let price = price_source
+            .get_price(collateral_type, config_account, clock)
+            .and_then(|price| {
+                if price.price <= 0 {
+                    return Err(ErrorCode::InvalidOraclePrice.into());
+                }
+                Ok((normalize_price(price.price as u64, price.exponent)?, price))
+            });
+        match price {
+            Ok(price) => prices.push(price),
+            Err(err) => {
+                msg!("oracle {} unavailable: {}", index, err);
+                if index == 0 {
+                    primary_error = Some(err);
+                }
+            }
+        }
+    }
+    if prices.is_empty() {
+        return Err(primary_error.unwrap_or_else(|| ErrorCode::InvalidOraclePrice.into()));
+    }
+
+    let (selected_price, selected) = match config_account.oracle_aggregation {
+        OracleAggregation::Fallback => prices[0],
+        OracleAggregation::Median => {
+            let mut sorted = prices.clone();
+            sorted.sort_by_key(|(normalized, _)| *normalized);
+            sorted[(sorted.len() - 1) / 2]
+        }
+    };
+
+    let max_deviation = mul_div_u64(
+        selected_price,
+        config_account.max_oracle_deviation_bps,
+        BPS_DENOMINATOR,
+        Rounding::Down,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    for (normalized, _) in prices.iter() {
+        if normalized.abs_diff(selected_price) > max_deviation {
+            msg!(
+                "oracle price deviation too large, selected:{}, other:{}",
+                selected_price,
+                normalized
+            );
+            return Err(ErrorCode::OracleDeviationTooLarge.into());
+        }
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::{assert_error, test_clock, test_collateral_type, test_config};
+
+    ///固定返回价格的价格来源，None表示价格不可用
+    struct FixedPriceSource(Option<i64>);
+
+    impl PriceSource for FixedPriceSource {
+        fn get_price(
+            &self,
+            _collateral_type: &CollateralType,
+            _config_account: &Config,
+            _clock: &Clock,
+        ) -> Result<OraclePrice> {
+            let price = self.0.ok_or(ErrorCode::OraclePriceTooOld)?;
+            Ok(OraclePrice {
+                price,
+                conf: 0,
+                exponent: -8,
+                publish_time: 0,
+            })
+        }
+    }
+
+    fn aggregate(aggregation: OracleAggregation, prices: &[Option<i64>]) -> Result<i64> {
+        let mut config = test_config();
+        config.oracle_aggregation = aggregation;
+        //1%
+        config.max_oracle_deviation_bps = 100;
+        let price_sources: Vec<Box<dyn PriceSource>> = prices
+            .iter()
+            .map(|price| Box::new(FixedPriceSource(*price)) as Box<dyn PriceSource>)
+            .collect();
+        aggregate_oracle_prices(
+            &price_sources,
+            &test_collateral_type(),
+            &config,
+            &test_clock(0, 0),
+        )
+        .map(|price| price.price)
+    }
+
+    #[test]
+    fn fallback_uses_primary_then_first_available_secondary() {
+        assert_eq!(
+            aggregate(
+                OracleAggregation::Fallback,
+                &[Some(15_000_000_000), Some(15_100_000_000)]
+            )
+            .unwrap(),
+            15_000_000_000
+        );
+        assert_eq!(
+            aggregate(
+                OracleAggregation::Fallback,
+                &[None, Some(15_100_000_000), Some(15_050_000_000)]
+            )
+            .unwrap(),
+            15_100_000_000
+        );
+        assert_error(
+            aggregate(OracleAggregation::Fallback, &[None, None]),
+            ErrorCode::OraclePriceTooOld,
+        );
+    }
+
+    #[test]
+    fn median_picks_middle_price() {
+        assert_eq!(
+            aggregate(
+                OracleAggregation::Median,
+                &[
+                    Some(15_100_000_000),
+                    Some(15_000_000_000),
+                    Some(15_050_000_000)
+                ]
+            )
+            .unwrap(),
+            15_050_000_000
+        );
+        //偶数个时取偏低的一个
+        assert_eq!(
+            aggregate(
+                OracleAggregation::Median,
+                &[Some(15_100_000_000), Some(15_000_000_000)]
+            )
+            .unwrap(),
+            15_000_000_000
+        );
+    }
+
+    #[test]
+    fn rejects_prices_deviating_beyond_tolerance() {
+        //备用预言机偏离主预言机2%
+        assert_error(
+            aggregate(
+                OracleAggregation::Fallback,
+                &[Some(15_000_000_000), Some(15_300_000_000)],
+            ),
+            ErrorCode::OracleDeviationTooLarge,
+        );
+        //中位数也不能掩盖偏离过大的价格
+        assert_error(
+            aggregate(
+                OracleAggregation::Median,
+                &[
+                    Some(15_000_000_000),
+                    Some(15_010_000_000),
+                    Some(20_000_000_000),
+                ],
+            ),
+            ErrorCode::OracleDeviationTooLarge,
+        );
+        //恰好1%时接受
+        assert_eq!(
+            aggregate(
+                OracleAggregation::Fallback,
+                &[Some(15_000_000_000), Some(15_150_000_000)]
+            )
+            .unwrap(),
+            15_000_000_000
+        );
+    }
+}
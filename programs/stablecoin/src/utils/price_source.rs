@@ -0,0 +1,304 @@
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config, OracleSource};
+use crate::utils::{PythPriceSource, SignedPriceSource, SwitchboardPriceSource};
+use anchor_lang::prelude::*;
+
+///预言机原始价格，真实价格为 price * 10^exponent，conf与price同精度
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+///价格来源，各实现负责校验价格账户、feed、时效后返回原始价格
+///feed id在加载时确定，最大延迟取自抵押资产类型账户
+pub trait PriceSource {
+    fn get_price(
+        &self,
+        collateral_type: &CollateralType,
+        config_account: &Config,
+        clock: &Clock,
+    ) -> Result<OraclePrice>;
+}
+
+///按价格来源类型加载价格账户
+///Pyth和Switchboard传入价格账户，管理员签名价格传入instructions sysvar
+pub fn load_price_source(
+    price_account: &AccountInfo,
+    source: OracleSource,
+    oracle_program: &Pubkey,
+    feed_id: [u8; 32],
+    config_account: &Config,
+) -> Result<Box<dyn PriceSource>> {
+    let price_source: Box<dyn PriceSource> = match source {
+        OracleSource::PythPull => Box::new(PythPriceSource::load(
+            price_account,
+            oracle_program,
+            feed_id,
+        )?),
+        OracleSource::Switchboard => Box::new(SwitchboardPriceSource::load(
+            price_account,
+            oracle_program,
+            feed_id,
+        )?),
+        OracleSource::SignedMessage => Box::new(SignedPriceSource::load(
+            price_account,
+            config_account,
+            feed_id,
+        )?),
+    };
+
+    Ok(price_source)
+}
+
+///加载主预言机账户和remaining_accounts中的备用预言机账户
+///主预言机使用市场配置的价格来源和抵押资产类型的feed id
+///备用预言机必须按抵押资产类型配置的顺序全部传入，且不能与其他预言机账户重复
+pub fn load_price_sources(
+    primary_price_account: &AccountInfo,
+    secondary_price_accounts: &[AccountInfo],
+    collateral_type: &CollateralType,
+    config_account: &Config,
+) -> Result<Vec<Box<dyn PriceSource>>> {
+    let secondary_oracles = &collateral_type.secondary_oracles;
+    if secondary_price_accounts.len() != secondary_oracles.len() {
+        return Err(ErrorCode::InvalidSecondaryOracleAccounts.into());
+    }
+    for (index, (price_account, oracle)) in secondary_price_accounts
+        .iter()
+        .zip(secondary_oracles.iter())
+        .enumerate()
+    {
+        if price_account.key() != oracle.price_account {
+            return Err(ErrorCode::InvalidSecondaryOracleAccounts.into());
+        }
+        if price_account.key() == primary_price_account.key()
+            || secondary_price_accounts[..index]
+                .iter()
+                .any(|other| other.key() == price_account.key())
+        {
+            return Err(ErrorCode::DuplicateOracleAccount.into());
+        }
+    }
+
+    let mut price_sources = Vec::with_capacity(1 + secondary_price_accounts.len());
+    price_sources.push(load_price_source(
+        primary_price_account,
+        config_account.oracle_source,
+        &config_account.oracle_receiver_program,
+        collateral_type.feed_id,
+        config_account,
+    )?);
+    for (price_account, oracle) in secondary_price_accounts
+        .iter()
+        .zip(secondary_oracles.iter())
+    {
+        price_sources.push(load_price_source(
+            price_account,
+            oracle.source,
+            &oracle.oracle_program,
+            oracle.feed_id,
+            config_account,
+        )?);
+    }
+
+    Ok(price_sources)
+}
+
+///检查价格发布时间是否在最大延迟内
+pub fn check_publish_time(publish_time: i64, max_age: u64, clock: &Clock) -> Result<()> {
+    let age = clock.unix_timestamp.saturating_sub(publish_time);
+    if age < 0 || age as u64 > max_age {
+        return Err(ErrorCode::OraclePriceTooOld.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::SecondaryOracle;
+    use crate::utils::test_util::{assert_error, test_collateral_type, test_config};
+    use crate::utils::{SwitchboardAggregatorRound, SwitchboardDecimal};
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn aggregator(owner: Pubkey) -> Self {
+            let round = SwitchboardAggregatorRound {
+                result: SwitchboardDecimal {
+                    mantissa: 1_500_000,
+                    scale: 4,
+                },
+                std_deviation: SwitchboardDecimal {
+                    mantissa: 0,
+                    scale: 4,
+                },
+                round_open_slot: 0,
+                round_open_timestamp: 0,
+            };
+            let mut data = vec![0u8; 8];
+            data.extend_from_slice(&round.try_to_vec().unwrap());
+            Self {
+                key: Pubkey::new_unique(),
+                owner,
+                lamports: 0,
+                data,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn secondary_oracle(price_account: Pubkey, oracle_program: Pubkey) -> SecondaryOracle {
+        SecondaryOracle {
+            source: OracleSource::Switchboard,
+            oracle_program,
+            price_account,
+            feed_id: price_account.to_bytes(),
+        }
+    }
+
+    fn switchboard_config(oracle_program: Pubkey) -> Config {
+        let mut config = test_config();
+        config.oracle_source = OracleSource::Switchboard;
+        config.oracle_receiver_program = oracle_program;
+        config
+    }
+
+    #[test]
+    fn loads_primary_and_configured_secondaries() {
+        let program = Pubkey::new_unique();
+        let config = switchboard_config(program);
+        let mut primary = TestAccount::aggregator(program);
+        let mut secondary_a = TestAccount::aggregator(program);
+        let mut secondary_b = TestAccount::aggregator(program);
+        let mut collateral_type = test_collateral_type();
+        collateral_type.feed_id = primary.key.to_bytes();
+        collateral_type.secondary_oracles = vec![
+            secondary_oracle(secondary_a.key, program),
+            secondary_oracle(secondary_b.key, program),
+        ];
+
+        let price_sources = load_price_sources(
+            &primary.info(),
+            &[secondary_a.info(), secondary_b.info()],
+            &collateral_type,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(price_sources.len(), 3);
+    }
+
+    #[test]
+    fn requires_every_configured_secondary() {
+        let program = Pubkey::new_unique();
+        let config = switchboard_config(program);
+        let mut primary = TestAccount::aggregator(program);
+        let mut secondary_a = TestAccount::aggregator(program);
+        let mut secondary_b = TestAccount::aggregator(program);
+        let mut other = TestAccount::aggregator(program);
+        let mut collateral_type = test_collateral_type();
+        collateral_type.feed_id = primary.key.to_bytes();
+        collateral_type.secondary_oracles = vec![
+            secondary_oracle(secondary_a.key, program),
+            secondary_oracle(secondary_b.key, program),
+        ];
+
+        //不传备用预言机
+        assert_error(
+            load_price_sources(&primary.info(), &[], &collateral_type, &config).map(|_| ()),
+            ErrorCode::InvalidSecondaryOracleAccounts,
+        );
+        //少传一个
+        assert_error(
+            load_price_sources(
+                &primary.info(),
+                &[secondary_a.info()],
+                &collateral_type,
+                &config,
+            )
+            .map(|_| ()),
+            ErrorCode::InvalidSecondaryOracleAccounts,
+        );
+        //传入未配置的账户
+        assert_error(
+            load_price_sources(
+                &primary.info(),
+                &[secondary_a.info(), other.info()],
+                &collateral_type,
+                &config,
+            )
+            .map(|_| ()),
+            ErrorCode::InvalidSecondaryOracleAccounts,
+        );
+        //顺序不一致
+        assert_error(
+            load_price_sources(
+                &primary.info(),
+                &[secondary_b.info(), secondary_a.info()],
+                &collateral_type,
+                &config,
+            )
+            .map(|_| ()),
+            ErrorCode::InvalidSecondaryOracleAccounts,
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_oracle_accounts() {
+        let program = Pubkey::new_unique();
+        let config = switchboard_config(program);
+        let mut primary = TestAccount::aggregator(program);
+        let mut primary_again = TestAccount {
+            key: primary.key,
+            ..TestAccount::aggregator(program)
+        };
+        let mut collateral_type = test_collateral_type();
+        collateral_type.feed_id = primary.key.to_bytes();
+        collateral_type.max_age = 60;
+        //备用预言机与主预言机是同一个账户
+        collateral_type.secondary_oracles = vec![secondary_oracle(primary.key, program)];
+        assert_error(
+            load_price_sources(
+                &primary.info(),
+                &[primary_again.info()],
+                &collateral_type,
+                &config,
+            )
+            .map(|_| ()),
+            ErrorCode::DuplicateOracleAccount,
+        );
+
+        //配置中重复的备用预言机
+        let secondary = Pubkey::new_unique();
+        collateral_type.secondary_oracles = vec![
+            secondary_oracle(secondary, program),
+            secondary_oracle(secondary, program),
+        ];
+        assert_error(
+            collateral_type.validate_oracle_parameters(),
+            ErrorCode::DuplicateOracleAccount,
+        );
+        collateral_type.secondary_oracles = vec![secondary_oracle(secondary, program)];
+        assert!(collateral_type.validate_oracle_parameters().is_ok());
+    }
+}
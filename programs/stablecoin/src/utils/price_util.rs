@@ -0,0 +1,189 @@
+use crate::constants::{BPS_DENOMINATOR, MINT_DECIMALS};
+use crate::errors::ErrorCode;
+use crate::events::PriceOverrideUsed;
+use crate::states::{CollateralType, Config, PriceHistory, PriceMode, PriceOverride};
+use crate::utils::{aggregate_oracle_prices, mul_div_u64, OraclePrice, PriceSource, Rounding};
+use anchor_lang::prelude::*;
+
+///换算到稳定币精度后的抵押资产价格，均为一个完整抵押资产单位对应的稳定币最小单位数量
+#[derive(Clone, Copy, Debug)]
+pub struct CollateralPrice {
+    //预言机报价
+    pub price: u64,
+    //price - k*conf，用于铸币、取回抵押资产时给抵押资产估值
+    pub lower: u64,
+    //price + k*conf，用于把债务换算成抵押资产数量(赎回、清算)
+    pub upper: u64,
+}
+
+impl CollateralPrice {
+    ///以新的价格为中心按相同比例缩放置信区间上下限
+    pub fn rebase(&self, price: u64) -> Option<CollateralPrice> {
+        Some(CollateralPrice {
+            price,
+            lower: mul_div_u64(self.lower, price, self.price, Rounding::Down)?,
+            upper: mul_div_u64(self.upper, price, self.price, Rounding::Up)?,
+        })
+    }
+}
+
+///按操作配置的价格模式选出计算健康因子使用的价格
+///非Spot模式需要传入价格历史账户
+pub fn select_collateral_price(
+    spot_price: CollateralPrice,
+    price_history: Option<&PriceHistory>,
+    price_mode: PriceMode,
+    config_account: &Config,
+    now: i64,
+) -> Result<CollateralPrice> {
+    if price_mode == PriceMode::Spot {
+        return Ok(spot_price);
+    }
+    let price_history = price_history.ok_or(ErrorCode::PriceHistoryRequired)?;
+    let twap = price_history
+        .calc_twap(now, config_account.twap_window_secs)
+        .ok_or(ErrorCode::TwapUnavailable)?;
+    msg!("spot price:{}, twap:{}", spot_price.price, twap);
+    let twap_price = spot_price.rebase(twap).ok_or(ErrorCode::MathOverflow)?;
+
+    let selected = match price_mode {
+        PriceMode::Spot => spot_price,
+        PriceMode::Twap => twap_price,
+        PriceMode::MinOfSpotAndTwap => CollateralPrice {
+            price: spot_price.price.min(twap_price.price),
+            lower: spot_price.lower.min(twap_price.lower),
+            upper: spot_price.upper.min(twap_price.upper),
+        },
+        PriceMode::MaxOfSpotAndTwap => CollateralPrice {
+            price: spot_price.price.max(twap_price.price),
+            lower: spot_price.lower.max(twap_price.lower),
+            upper: spot_price.upper.max(twap_price.upper),
+        },
+    };
+
+    Ok(selected)
+}
+
+///铸币、还款、取回和清算使用的抵押资产价格
+///优先使用预言机价格，预言机价格过期或没有可用的价格来源时使用生效中的管理员覆盖价格
+///预言机过期且抵押资产类型有未失效的覆盖价格时必须传入覆盖价格账户，调用人不能通过不传入该账户回避覆盖价格
+pub fn get_collateral_price(
+    price_sources: &[Box<dyn PriceSource>],
+    price_override: Option<&PriceOverride>,
+    collateral_type: &CollateralType,
+    config_account: &Config,
+) -> Result<CollateralPrice> {
+    let oracle_error =
+        match get_oracle_collateral_price(price_sources, collateral_type, config_account) {
+            Ok(price) => return Ok(price),
+            Err(err) => err,
+        };
+    //置信区间过大、价格偏离等错误不使用覆盖价格
+    if !price_sources.is_empty() && oracle_error != error!(ErrorCode::OraclePriceTooOld) {
+        return Err(oracle_error);
+    }
+    let clock = Clock::get()?;
+    if clock.unix_timestamp >= collateral_type.price_override_expires_at {
+        return Err(oracle_error);
+    }
+    let price_override = price_override.ok_or(ErrorCode::PriceOverrideRequired)?;
+    if !price_override.is_active(clock.unix_timestamp) {
+        return Err(oracle_error);
+    }
+    msg!("use price override");
+    emit!(PriceOverrideUsed {
+        collateral_type: price_override.collateral_type,
+        price: price_override.price,
+        conf: price_override.conf,
+        exponent: price_override.exponent,
+        used_at: clock.unix_timestamp,
+    });
+    to_collateral_price(
+        OraclePrice {
+            price: price_override.price,
+            conf: price_override.conf,
+            exponent: price_override.exponent,
+            publish_time: price_override.effective_at,
+        },
+        config_account,
+    )
+}
+
+///只使用预言机的抵押资产价格，价格feed和最大延迟取自抵押资产类型账户
+pub fn get_oracle_collateral_price(
+    price_sources: &[Box<dyn PriceSource>],
+    collateral_type: &CollateralType,
+    config_account: &Config,
+) -> Result<CollateralPrice> {
+    let clock = Clock::get()?;
+    let price = aggregate_oracle_prices(price_sources, collateral_type, config_account, &clock)?;
+    to_collateral_price(price, config_account)
+}
+
+///检查置信区间并把原始价格换算成稳定币精度
+///例如SOL价格为150美元时price为150 * 10^MINT_DECIMALS
+fn to_collateral_price(price: OraclePrice, config_account: &Config) -> Result<CollateralPrice> {
+    msg!(
+        "oracle price:{}, conf:{}, exponent:{}",
+        price.price,
+        price.conf,
+        price.exponent
+    );
+    if price.price <= 0 {
+        return Err(ErrorCode::InvalidOraclePrice.into());
+    }
+    let raw_price = price.price as u64;
+
+    //置信区间占价格比例超过上限时拒绝该价格
+    let max_conf = mul_div_u64(
+        raw_price,
+        config_account.max_oracle_conf_bps,
+        BPS_DENOMINATOR,
+        Rounding::Down,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    if price.conf > max_conf {
+        return Err(ErrorCode::OracleConfidenceTooWide.into());
+    }
+
+    let adjusted_conf = mul_div_u64(
+        price.conf,
+        config_account.oracle_conf_multiplier_bps,
+        BPS_DENOMINATOR,
+        Rounding::Up,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    let lower = raw_price
+        .checked_sub(adjusted_conf)
+        .ok_or(ErrorCode::InvalidOraclePrice)?;
+    let upper = raw_price
+        .checked_add(adjusted_conf)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(CollateralPrice {
+        price: normalize_price(raw_price, price.exponent)?,
+        lower: normalize_price(lower, price.exponent)?,
+        upper: normalize_price(upper, price.exponent)?,
+    })
+}
+
+///把 price * 10^exponent 的预言机价格换算到稳定币精度(MINT_DECIMALS)
+///需要缩小精度时向下取整，价格必须为正
+pub fn normalize_price(price: u64, exponent: i32) -> Result<u64> {
+    let scale = exponent
+        .checked_add(MINT_DECIMALS as i32)
+        .ok_or(ErrorCode::InvalidOraclePrice)?;
+    let factor = 10u64
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(ErrorCode::InvalidOraclePrice)?;
+    let normalized = if scale >= 0 {
+        price.checked_mul(factor).ok_or(ErrorCode::MathOverflow)?
+    } else {
+        price / factor
+    };
+    if normalized == 0 {
+        return Err(ErrorCode::InvalidOraclePrice.into());
+    }
+
+    Ok(normalized)
+}
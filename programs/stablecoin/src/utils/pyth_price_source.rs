@@ -0,0 +1,85 @@
+use crate::constants::MIN_PARTIAL_VERIFICATION_SIGNATURES;
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config};
+use crate::utils::{OraclePrice, PriceSource};
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::error::GetPriceError;
+use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, VerificationLevel};
+
+///Pyth pull预言机价格账户(PriceUpdateV2)
+pub struct PythPriceSource {
+    pub price_update: PriceUpdateV2,
+    pub feed_id: [u8; 32],
+}
+
+impl PythPriceSource {
+    ///账户必须属于Pyth receiver程序且数据能反序列化为PriceUpdateV2
+    pub fn load(
+        price_update_account: &AccountInfo,
+        receiver_program: &Pubkey,
+        feed_id: [u8; 32],
+    ) -> Result<Self> {
+        if price_update_account.owner != receiver_program {
+            msg!("price update account owner:{}", price_update_account.owner);
+            return Err(ErrorCode::InvalidOracleAccount.into());
+        }
+        let price_update_data = price_update_account.try_borrow_data()?;
+        let mut price_update_data = price_update_data.iter().as_slice();
+        let price_update = PriceUpdateV2::try_deserialize(&mut price_update_data)
+            .map_err(|_| error!(ErrorCode::InvalidOracleAccount))?;
+        Ok(Self {
+            price_update,
+            feed_id,
+        })
+    }
+}
+
+///市场要求的最低验证级别
+pub fn min_verification_level(config_account: &Config) -> VerificationLevel {
+    if config_account.require_full_verification {
+        VerificationLevel::Full
+    } else {
+        VerificationLevel::Partial {
+            num_signatures: MIN_PARTIAL_VERIFICATION_SIGNATURES,
+        }
+    }
+}
+
+impl PriceSource for PythPriceSource {
+    fn get_price(
+        &self,
+        collateral_type: &CollateralType,
+        config_account: &Config,
+        clock: &Clock,
+    ) -> Result<OraclePrice> {
+        if collateral_type.max_age_slots > 0
+            && clock.slot.saturating_sub(self.price_update.posted_slot)
+                > collateral_type.max_age_slots
+        {
+            return Err(ErrorCode::OraclePriceTooOld.into());
+        }
+        let price = self
+            .price_update
+            .get_price_no_older_than_with_custom_verification_level(
+                clock,
+                collateral_type.max_age,
+                &self.feed_id,
+                min_verification_level(config_account),
+            )
+            .map_err(|e| match e {
+                GetPriceError::PriceTooOld => error!(ErrorCode::OraclePriceTooOld),
+                GetPriceError::MismatchedFeedId => error!(ErrorCode::OracleFeedIdMismatch),
+                GetPriceError::InsufficientVerificationLevel => {
+                    error!(ErrorCode::OracleVerificationLevelTooLow)
+                }
+                _ => error!(ErrorCode::InvalidOraclePrice),
+            })?;
+
+        Ok(OraclePrice {
+            price: price.price,
+            conf: price.conf,
+            exponent: price.exponent,
+            publish_time: price.publish_time,
+        })
+    }
+}
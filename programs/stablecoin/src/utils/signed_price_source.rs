@@ -0,0 +1,255 @@
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config};
+use crate::utils::{check_publish_time, OraclePrice, PriceSource};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked, ID as INSTRUCTIONS_SYSVAR_ID,
+};
+
+//ed25519指令数据中签名数量和padding占2字节，每个签名的offsets占14字节
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+
+///管理员签名的价格消息，由同一交易中的ed25519指令验证签名
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SignedPriceMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+///由Config.price_signer签名的价格消息集合
+pub struct SignedPriceSource {
+    pub messages: Vec<SignedPriceMessage>,
+    pub feed_id: [u8; 32],
+}
+
+impl SignedPriceSource {
+    ///价格账户传入instructions sysvar，读取当前指令之前所有ed25519指令中price_signer签名的价格消息
+    pub fn load(
+        instructions_sysvar: &AccountInfo,
+        config_account: &Config,
+        feed_id: [u8; 32],
+    ) -> Result<Self> {
+        if instructions_sysvar.key() != INSTRUCTIONS_SYSVAR_ID {
+            return Err(ErrorCode::InvalidOracleAccount.into());
+        }
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+        let mut messages = Vec::new();
+        for index in 0..current_index {
+            let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+            if instruction.program_id != ed25519_program::ID {
+                continue;
+            }
+            messages.extend(parse_ed25519_instruction(
+                &instruction.data,
+                &config_account.price_signer,
+            )?);
+        }
+        Ok(Self { messages, feed_id })
+    }
+}
+
+///解析ed25519指令，只接受签名数据都在本指令内且公钥为price_signer的价格消息
+fn parse_ed25519_instruction(
+    data: &[u8],
+    price_signer: &Pubkey,
+) -> Result<Vec<SignedPriceMessage>> {
+    let read_u16 = |offset: usize| -> Result<usize> {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+            .ok_or(error!(ErrorCode::InvalidOracleAccount))
+    };
+    let num_signatures = *data.first().ok_or(ErrorCode::InvalidOracleAccount)? as usize;
+    let mut messages = Vec::new();
+    for i in 0..num_signatures {
+        let start = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let public_key_offset = read_u16(start + 4)?;
+        let message_offset = read_u16(start + 8)?;
+        let message_size = read_u16(start + 10)?;
+        //signature/public key/message必须引用ed25519指令自身的数据
+        if read_u16(start + 2)? != u16::MAX as usize
+            || read_u16(start + 6)? != u16::MAX as usize
+            || read_u16(start + 12)? != u16::MAX as usize
+        {
+            continue;
+        }
+        let public_key = data
+            .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+            .ok_or(ErrorCode::InvalidOracleAccount)?;
+        if public_key != price_signer.as_ref() {
+            continue;
+        }
+        let mut message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidOracleAccount)?;
+        let message = SignedPriceMessage::deserialize(&mut message)
+            .map_err(|_| error!(ErrorCode::InvalidOracleAccount))?;
+        messages.push(message);
+    }
+
+    Ok(messages)
+}
+
+impl PriceSource for SignedPriceSource {
+    fn get_price(
+        &self,
+        collateral_type: &CollateralType,
+        _config_account: &Config,
+        clock: &Clock,
+    ) -> Result<OraclePrice> {
+        let message = self
+            .messages
+            .iter()
+            .filter(|message| message.feed_id == self.feed_id)
+            .max_by_key(|message| message.publish_time)
+            .ok_or(ErrorCode::OracleFeedIdMismatch)?;
+        check_publish_time(message.publish_time, collateral_type.max_age, clock)?;
+
+        Ok(OraclePrice {
+            price: message.price,
+            conf: message.conf,
+            exponent: message.exponent,
+            publish_time: message.publish_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::{assert_error, test_clock, test_collateral_type, test_config};
+
+    const FEED_ID: [u8; 32] = [7u8; 32];
+
+    fn price_message(price: i64, publish_time: i64) -> SignedPriceMessage {
+        SignedPriceMessage {
+            feed_id: FEED_ID,
+            price,
+            conf: 10,
+            exponent: -8,
+            publish_time,
+        }
+    }
+
+    ///按ed25519程序的布局构造单个签名的指令数据，instruction_index为u16::MAX表示引用本指令
+    fn ed25519_instruction_data(
+        signer: &Pubkey,
+        message: &SignedPriceMessage,
+        instruction_index: u16,
+    ) -> Vec<u8> {
+        let message = message.try_to_vec().unwrap();
+        let public_key_offset = (ED25519_HEADER_LEN + ED25519_OFFSETS_LEN) as u16;
+        let signature_offset = public_key_offset + PUBKEY_LEN as u16;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset,
+            instruction_index,
+            public_key_offset,
+            instruction_index,
+            message_offset,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        //签名由ed25519程序校验，这里只检查偏移和公钥
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(&message);
+        data
+    }
+
+    #[test]
+    fn parse_accepts_message_signed_by_price_signer() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_instruction_data(&signer, &price_message(15_000_000_000, 100), u16::MAX);
+        let messages = parse_ed25519_instruction(&data, &signer).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].feed_id, FEED_ID);
+        assert_eq!(messages[0].price, 15_000_000_000);
+        assert_eq!(messages[0].publish_time, 100);
+    }
+
+    #[test]
+    fn parse_ignores_message_from_other_signer() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_instruction_data(
+            &Pubkey::new_unique(),
+            &price_message(15_000_000_000, 100),
+            u16::MAX,
+        );
+        assert!(parse_ed25519_instruction(&data, &signer)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_ignores_data_from_other_instruction() {
+        //签名数据引用其他指令时，本指令中的公钥和消息可能并未被验证
+        let signer = Pubkey::new_unique();
+        let data = ed25519_instruction_data(&signer, &price_message(15_000_000_000, 100), 0);
+        assert!(parse_ed25519_instruction(&data, &signer)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_tampered_message() {
+        let signer = Pubkey::new_unique();
+        let mut data =
+            ed25519_instruction_data(&signer, &price_message(15_000_000_000, 100), u16::MAX);
+        //消息被截断
+        data.truncate(data.len() - 4);
+        assert_error(
+            parse_ed25519_instruction(&data, &signer),
+            ErrorCode::InvalidOracleAccount,
+        );
+        //offsets指向数据之外
+        let mut data =
+            ed25519_instruction_data(&signer, &price_message(15_000_000_000, 100), u16::MAX);
+        data[ED25519_HEADER_LEN + 4..ED25519_HEADER_LEN + 6]
+            .copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_error(
+            parse_ed25519_instruction(&data, &signer),
+            ErrorCode::InvalidOracleAccount,
+        );
+    }
+
+    #[test]
+    fn get_price_uses_latest_message_for_feed() {
+        let mut collateral_type = test_collateral_type();
+        collateral_type.max_age = 60;
+        let mut other_feed = price_message(1, 120);
+        other_feed.feed_id = [8u8; 32];
+        let mut price_source = SignedPriceSource {
+            messages: vec![
+                price_message(14_000_000_000, 90),
+                price_message(15_000_000_000, 110),
+                other_feed,
+            ],
+            feed_id: FEED_ID,
+        };
+
+        let price = price_source
+            .get_price(&collateral_type, &test_config(), &test_clock(130, 0))
+            .unwrap();
+        assert_eq!(price.price, 15_000_000_000);
+        assert_eq!(price.publish_time, 110);
+
+        assert_error(
+            price_source.get_price(&collateral_type, &test_config(), &test_clock(200, 0)),
+            ErrorCode::OraclePriceTooOld,
+        );
+        price_source.feed_id = [9u8; 32];
+        assert_error(
+            price_source.get_price(&collateral_type, &test_config(), &test_clock(130, 0)),
+            ErrorCode::OracleFeedIdMismatch,
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::constants::{BPS_DENOMINATOR, DEBT_INDEX_PRECISION, SECONDS_PER_YEAR, WAD};
+use crate::errors::ErrorCode;
+use crate::states::Config;
+use crate::utils::{mul_div, rpow_wad, Rounding};
+use anchor_lang::prelude::*;
+
+///按年化稳定费率把债务利率指数累计到当前时间
+///稳定费率按秒复利：每秒利率为 stability_fee_bps / SECONDS_PER_YEAR，累计结果与调用频率无关
+///每条涉及债务的指令开始时都会先调用，也可以通过accrue指令单独触发
+pub fn accrue_stability_fee(config_account: &mut Config, now: i64) -> Result<()> {
+    if now <= config_account.last_accrual_time {
+        return Ok(());
+    }
+    let elapsed = (now - config_account.last_accrual_time) as u64;
+    let rate_per_second = WAD
+        .checked_add(
+            mul_div(
+                config_account.stability_fee_bps as u128,
+                WAD,
+                BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128,
+                Rounding::Down,
+            )
+            .ok_or(ErrorCode::MathOverflow)?,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+    let growth = rpow_wad(rate_per_second, elapsed).ok_or(ErrorCode::MathOverflow)?;
+    config_account.debt_index = mul_div(config_account.debt_index, growth, WAD, Rounding::Down)
+        .ok_or(ErrorCode::MathOverflow)?;
+    config_account.last_accrual_time = now;
+    msg!("debt index after accrue:{}", config_account.debt_index);
+
+    Ok(())
+}
+
+///本金化债务按当前利率指数换算成实际债务，向上取整
+pub fn calc_debt_from_normalized(normalized_debt: u64, debt_index: u128) -> Option<u64> {
+    let debt = mul_div(
+        normalized_debt as u128,
+        debt_index,
+        DEBT_INDEX_PRECISION,
+        Rounding::Up,
+    )?;
+    u64::try_from(debt).ok()
+}
+
+///新增债务对应的本金化债务，向上取整
+pub fn calc_normalized_debt_to_add(amount: u64, debt_index: u128) -> Option<u64> {
+    let normalized = mul_div(amount as u128, DEBT_INDEX_PRECISION, debt_index, Rounding::Up)?;
+    u64::try_from(normalized).ok()
+}
+
+///偿还债务对应减少的本金化债务，向下取整；还清全部债务时返回全部本金化债务
+pub fn calc_normalized_debt_to_repay(
+    amount: u64,
+    normalized_debt: u64,
+    debt_index: u128,
+) -> Option<u64> {
+    if amount >= calc_debt_from_normalized(normalized_debt, debt_index)? {
+        return Some(normalized_debt);
+    }
+    let normalized = mul_div(amount as u128, DEBT_INDEX_PRECISION, debt_index, Rounding::Down)?;
+    Some(u64::try_from(normalized).ok()?.min(normalized_debt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::test_config;
+
+    fn fee_config() -> Config {
+        let mut config = test_config();
+        config.stability_fee_bps = 200;
+        config.debt_index = DEBT_INDEX_PRECISION;
+        config
+    }
+
+    #[test]
+    fn one_year_compounds_per_second() {
+        let mut config = fee_config();
+        accrue_stability_fee(&mut config, SECONDS_PER_YEAR as i64).unwrap();
+        //2%按秒复利一年约为e^0.02 - 1 = 2.0201%
+        assert!(config.debt_index > 1_020_201_000_000_000_000);
+        assert!(config.debt_index < 1_020_202_000_000_000_000);
+        assert_eq!(config.last_accrual_time, SECONDS_PER_YEAR as i64);
+    }
+
+    #[test]
+    fn accrual_does_not_depend_on_call_frequency() {
+        let mut once = fee_config();
+        accrue_stability_fee(&mut once, SECONDS_PER_YEAR as i64).unwrap();
+
+        let mut daily = fee_config();
+        for day in 1..=365 {
+            accrue_stability_fee(&mut daily, day * 86_400).unwrap();
+        }
+        //只有取整误差
+        assert!(once.debt_index.abs_diff(daily.debt_index) < 1_000_000);
+
+        let mut idle = fee_config();
+        accrue_stability_fee(&mut idle, 0).unwrap();
+        assert_eq!(idle.debt_index, DEBT_INDEX_PRECISION);
+    }
+}
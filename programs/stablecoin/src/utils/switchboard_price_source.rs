@@ -0,0 +1,206 @@
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config};
+use crate::utils::{check_publish_time, OraclePrice, PriceSource};
+use anchor_lang::prelude::*;
+
+///Switchboard风格的十进制数，值为 mantissa / 10^scale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwitchboardDecimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+///聚合器账户中最新一轮结果的布局，位于8字节discriminator之后
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwitchboardAggregatorRound {
+    pub result: SwitchboardDecimal,
+    pub std_deviation: SwitchboardDecimal,
+    pub round_open_slot: u64,
+    pub round_open_timestamp: i64,
+}
+
+///Switchboard风格的聚合器价格账户，feed_id为聚合器账户地址
+pub struct SwitchboardPriceSource {
+    pub aggregator: Pubkey,
+    pub round: SwitchboardAggregatorRound,
+    pub feed_id: [u8; 32],
+}
+
+impl SwitchboardPriceSource {
+    ///账户必须属于Switchboard程序
+    pub fn load(
+        aggregator_account: &AccountInfo,
+        oracle_program: &Pubkey,
+        feed_id: [u8; 32],
+    ) -> Result<Self> {
+        if aggregator_account.owner != oracle_program {
+            msg!("aggregator account owner:{}", aggregator_account.owner);
+            return Err(ErrorCode::InvalidOracleAccount.into());
+        }
+        let data = aggregator_account.try_borrow_data()?;
+        let mut round_data = data.get(8..).ok_or(ErrorCode::InvalidOracleAccount)?;
+        let round = SwitchboardAggregatorRound::deserialize(&mut round_data)
+            .map_err(|_| error!(ErrorCode::InvalidOracleAccount))?;
+        Ok(Self {
+            aggregator: aggregator_account.key(),
+            round,
+            feed_id,
+        })
+    }
+}
+
+///把mantissa从from_scale换算到to_scale(to_scale <= from_scale时向下取整)
+fn rescale_mantissa(mantissa: i128, from_scale: u32, to_scale: u32) -> Option<i128> {
+    if to_scale >= from_scale {
+        mantissa.checked_mul(10i128.checked_pow(to_scale - from_scale)?)
+    } else {
+        mantissa.checked_div(10i128.checked_pow(from_scale - to_scale)?)
+    }
+}
+
+impl PriceSource for SwitchboardPriceSource {
+    fn get_price(
+        &self,
+        collateral_type: &CollateralType,
+        _config_account: &Config,
+        clock: &Clock,
+    ) -> Result<OraclePrice> {
+        if self.aggregator.to_bytes() != self.feed_id {
+            return Err(ErrorCode::OracleFeedIdMismatch.into());
+        }
+        if collateral_type.max_age_slots > 0
+            && clock.slot.saturating_sub(self.round.round_open_slot) > collateral_type.max_age_slots
+        {
+            return Err(ErrorCode::OraclePriceTooOld.into());
+        }
+        check_publish_time(
+            self.round.round_open_timestamp,
+            collateral_type.max_age,
+            clock,
+        )?;
+
+        let scale = self.round.result.scale;
+        let price = i64::try_from(self.round.result.mantissa)
+            .map_err(|_| error!(ErrorCode::InvalidOraclePrice))?;
+        let conf = rescale_mantissa(
+            self.round.std_deviation.mantissa,
+            self.round.std_deviation.scale,
+            scale,
+        )
+        .and_then(|conf| u64::try_from(conf).ok())
+        .ok_or(ErrorCode::InvalidOraclePrice)?;
+        let exponent = -i32::try_from(scale).map_err(|_| error!(ErrorCode::InvalidOraclePrice))?;
+
+        Ok(OraclePrice {
+            price,
+            conf,
+            exponent,
+            publish_time: self.round.round_open_timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::{assert_error, test_clock, test_collateral_type, test_config};
+
+    fn aggregator_data(round: &SwitchboardAggregatorRound) -> Vec<u8> {
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(&round.try_to_vec().unwrap());
+        data
+    }
+
+    fn round(open_slot: u64, open_timestamp: i64) -> SwitchboardAggregatorRound {
+        SwitchboardAggregatorRound {
+            //150.25，scale为4
+            result: SwitchboardDecimal {
+                mantissa: 1_502_500,
+                scale: 4,
+            },
+            //0.5，scale为2，换算到scale 4为5000
+            std_deviation: SwitchboardDecimal {
+                mantissa: 50,
+                scale: 2,
+            },
+            round_open_slot: open_slot,
+            round_open_timestamp: open_timestamp,
+        }
+    }
+
+    #[test]
+    fn load_and_get_price_from_aggregator_account() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let config = test_config();
+        let mut collateral_type = test_collateral_type();
+        collateral_type.max_age = 60;
+        collateral_type.max_age_slots = 10;
+        let mut lamports = 0;
+        let mut data = aggregator_data(&round(1_000, 100));
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        let mut price_source =
+            SwitchboardPriceSource::load(&account, &owner, key.to_bytes()).unwrap();
+        let price = price_source
+            .get_price(&collateral_type, &config, &test_clock(120, 1_005))
+            .unwrap();
+        assert_eq!(price.price, 1_502_500);
+        assert_eq!(price.conf, 5_000);
+        assert_eq!(price.exponent, -4);
+        assert_eq!(price.publish_time, 100);
+
+        assert_error(
+            price_source.get_price(&collateral_type, &config, &test_clock(120, 1_011)),
+            ErrorCode::OraclePriceTooOld,
+        );
+        assert_error(
+            price_source.get_price(&collateral_type, &config, &test_clock(161, 1_005)),
+            ErrorCode::OraclePriceTooOld,
+        );
+        price_source.feed_id = Pubkey::new_unique().to_bytes();
+        assert_error(
+            price_source.get_price(&collateral_type, &config, &test_clock(120, 1_005)),
+            ErrorCode::OracleFeedIdMismatch,
+        );
+    }
+
+    #[test]
+    fn load_rejects_account_of_other_program() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = aggregator_data(&round(1_000, 100));
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_error(
+            SwitchboardPriceSource::load(&account, &Pubkey::new_unique(), key.to_bytes())
+                .map(|_| ()),
+            ErrorCode::InvalidOracleAccount,
+        );
+    }
+
+    #[test]
+    fn rescale_mantissa_between_scales() {
+        assert_eq!(rescale_mantissa(50, 2, 4), Some(5_000));
+        assert_eq!(rescale_mantissa(5_099, 4, 2), Some(50));
+        assert_eq!(rescale_mantissa(1, 0, 40), None);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::errors::ErrorCode;
+use crate::states::{CollateralType, Config};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_error::ProgramError;
+
+///字段全为0(枚举取第一个值)的市场配置，测试中按需修改字段
+pub fn test_config() -> Config {
+    let data = vec![0u8; 8 + Config::INIT_SPACE];
+    Config::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+}
+
+///字段全为0的抵押资产类型，测试中按需修改字段
+pub fn test_collateral_type() -> CollateralType {
+    let data = vec![0u8; 8 + CollateralType::INIT_SPACE];
+    CollateralType::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+}
+
+pub fn test_clock(unix_timestamp: i64, slot: u64) -> Clock {
+    Clock {
+        slot,
+        unix_timestamp,
+        ..Clock::default()
+    }
+}
+
+///比较错误码，忽略error!记录的源码位置
+pub fn assert_error<T: std::fmt::Debug>(result: Result<T>, expected: ErrorCode) {
+    let err = result.expect_err("expected error");
+    assert_eq!(
+        ProgramError::from(err),
+        ProgramError::from(Error::from(expected))
+    );
+}
@@ -0,0 +1,28 @@
+use crate::constants::{BPS_DENOMINATOR, MINT_DECIMALS};
+use crate::utils::{mul_div_u64, Rounding};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
+
+///按bps计算手续费，向上取整
+pub fn calc_fee(amount: u64, fee_bps: u64) -> Option<u64> {
+    mul_div_u64(amount, fee_bps, BPS_DENOMINATOR, Rounding::Up)
+}
+
+///把稳定币手续费从用户账户转入国库稳定币账户
+pub fn pay_fee_to_treasury<'info>(
+    fee: u64,
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    program: AccountInfo<'info>,
+) -> Result<()> {
+    let accounts = TransferChecked {
+        from,
+        mint,
+        to,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new(program, accounts);
+    transfer_checked(cpi_ctx, fee, MINT_DECIMALS)
+}
@@ -2,7 +2,17 @@ import * as anchor from "@coral-xyz/anchor";
 import {Program} from "@coral-xyz/anchor";
 import {Stablecoin} from "../target/types/stablecoin";
 import {PythSolanaReceiver} from "@pythnetwork/pyth-solana-receiver";
-import {getMint, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync, ASSOCIATED_TOKEN_PROGRAM_ID} from "@solana/spl-token";
+import {expect} from "chai";
+import {
+    createMint,
+    getMint,
+    TOKEN_2022_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+    NATIVE_MINT,
+    getAssociatedTokenAddressSync,
+    createWrappedNativeAccount,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+} from "@solana/spl-token";
 
 describe("stablecoin", () => {
     // Configure the client to use the local cluster.
@@ -21,8 +31,10 @@ describe("stablecoin", () => {
     const timestamp = new anchor.BN(new Date().getTime()); // 使用BN处理大数
     console.log("timestamp:", timestamp.toNumber());
 
+    //抵押资产使用wrapped SOL(spl token)
+    const collateralMint = NATIVE_MINT;
     const [collateralAccountPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("collateral_account"), wallet.publicKey.toBuffer(), timestamp.toArrayLike(Buffer, 'le', 8)],
+        [Buffer.from("collateral_account"), collateralMint.toBuffer(), wallet.publicKey.toBuffer(), timestamp.toArrayLike(Buffer, 'le', 8)],
         programId
     );
     console.log("collateralAccountPDA:", collateralAccountPDA);
@@ -39,12 +51,37 @@ describe("stablecoin", () => {
     )
     console.log("configAccountPDA:", configAccountPDA);
 
+    const [collateralTypePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral_type_account"), configAccountPDA.toBuffer(), collateralMint.toBuffer()],
+        programId
+    )
+    console.log("collateralTypePDA:", collateralTypePDA);
+
+    //风险参数修改的排队账户：市场级修改使用默认公钥作为种子，抵押资产类型的修改使用该类型账户
+    const [marketPendingConfigChangePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_config_change_account"), configAccountPDA.toBuffer(), anchor.web3.PublicKey.default.toBuffer()],
+        programId
+    )
+    const [collateralPendingConfigChangePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_config_change_account"), configAccountPDA.toBuffer(), collateralTypePDA.toBuffer()],
+        programId
+    )
+
     const [depositedAssetAccountPAD] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("deposit_asset_account"), wallet.publicKey.toBuffer(), timestamp.toArrayLike(Buffer, 'le', 8)],
+        [Buffer.from("deposit_asset_account"), collateralAccountPDA.toBuffer()],
         programId
     )
     console.log("depositedAssetAccountPAD:", depositedAssetAccountPAD);
 
+    const depositorCollateralAccount = getAssociatedTokenAddressSync(
+        collateralMint,
+        wallet.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    console.log("depositorCollateralAccount:", depositorCollateralAccount);
+
     const receiveStablecoinAccount = getAssociatedTokenAddressSync(
         mintAccountPDA,
         wallet.publicKey,
@@ -54,22 +91,117 @@ describe("stablecoin", () => {
     );
     console.log("receiveStablecoinAccount:", receiveStablecoinAccount);
 
+    const [treasuryAccountPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury_account"), configAccountPDA.toBuffer()],
+        programId
+    )
+    console.log("treasuryAccountPDA:", treasuryAccountPDA);
+
+    const treasuryStablecoinAccount = getAssociatedTokenAddressSync(
+        mintAccountPDA,
+        treasuryAccountPDA,
+        true,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    console.log("treasuryStablecoinAccount:", treasuryStablecoinAccount);
+
+    //期望交易因指定的程序错误失败，不跳过预检以便从日志中解析错误码
+    const expectProgramError = async (request: Promise<unknown>, code: string) => {
+        try {
+            await request;
+        } catch (err) {
+            expect(err).to.be.instanceOf(anchor.AnchorError);
+            expect((err as anchor.AnchorError).error.errorCode.code).to.equal(code);
+            return;
+        }
+        expect.fail(`expected ${code} error`);
+    };
+
+    //只修改指定字段的update config参数
+    const updateConfigParams = (changes: object) => ({
+        stabilityFeeBps: null,
+        debtCeiling: null,
+        mintFeeBps: null,
+        redeemFeeBps: null,
+        liquidationProtocolShareBps: null,
+        closeFactorBps: null,
+        oracleConfMultiplierBps: null,
+        maxOracleConfBps: null,
+        requireFullVerification: null,
+        oracleSource: null,
+        oracleReceiverProgram: null,
+        priceSigner: null,
+        oracleAggregation: null,
+        maxOracleDeviationBps: null,
+        twapWindowSecs: null,
+        mintPriceMode: null,
+        redeemPriceMode: null,
+        liquidationPriceMode: null,
+        circuitBreakerBps: null,
+        circuitBreakerWindowSecs: null,
+        circuitBreakerCooldownSecs: null,
+        priceOverrideTimelockSecs: null,
+        maxPriceOverrideDurationSecs: null,
+        ...changes,
+    });
+
+    //只修改指定字段的update collateral type参数
+    const updateCollateralTypeParams = (changes: object) => ({
+        feedId: null,
+        maxAge: null,
+        maxAgeSlots: null,
+        secondaryOracles: null,
+        debtCeiling: null,
+        enabled: null,
+        ...changes,
+    });
+
     console.log("===========================================================================")
     it("init config......", async () => {
         // console.log("Token Program ID:", TOKEN_2022_PROGRAM_ID.toBase58());
         const sig = await program.methods
             .processInitConfig(
                 timestamp,
-                new anchor.BN(80),
-                new anchor.BN(90),
-                new anchor.BN(10),
-                new anchor.BN(100),
+                {
+                    maxLtv: new anchor.BN(80),
+                    liquidationThreshold: new anchor.BN(90),
+                    liquidationBonus: new anchor.BN(10),
+                    minHealthFactor: new anchor.BN(100),
+                    riskParamTimelockSecs: new anchor.BN(0),//测试中排队后立即执行
+                    stabilityFeeBps: new anchor.BN(200),//年化稳定费率2%
+                    debtCeiling: new anchor.BN("10000000000000000"),//市场总债务上限
+                    mintFeeBps: new anchor.BN(30),//铸币手续费0.3%
+                    redeemFeeBps: new anchor.BN(30),//赎回手续费0.3%
+                    liquidationProtocolShareBps: new anchor.BN(2000),//清算奖励的20%归国库
+                    closeFactorBps: new anchor.BN(5000),//单次清算最多偿还50%债务
+                    oracleConfMultiplierBps: new anchor.BN(10000),//k=1
+                    maxOracleConfBps: new anchor.BN(200),//置信区间不超过价格的2%
+                    requireFullVerification: true,
+                    oracleSource: {pythPull: {}},
+                    oracleReceiverProgram: pythSolanaReceiver.receiver.programId,
+                    priceSigner: wallet.publicKey,
+                    oracleAggregation: {fallback: {}},
+                    maxOracleDeviationBps: new anchor.BN(100),//各预言机价格偏离不超过1%
+                    twapWindowSecs: new anchor.BN(1800),
+                    mintPriceMode: {spot: {}},
+                    redeemPriceMode: {spot: {}},
+                    liquidationPriceMode: {spot: {}},
+                    circuitBreakerBps: new anchor.BN(2000),//价格10分钟内偏离超过20%时熔断
+                    circuitBreakerWindowSecs: new anchor.BN(600),
+                    circuitBreakerCooldownSecs: new anchor.BN(3600),
+                    priceOverrideTimelockSecs: new anchor.BN(3600),
+                    maxPriceOverrideDurationSecs: new anchor.BN(86400),
+                },
             ).accountsStrict({
                 authority: wallet.publicKey,
                 mintAccount: mintAccountPDA,
                 configAccount: configAccountPDA,
+                treasuryAccount: treasuryAccountPDA,
+                treasuryStablecoinAccount: treasuryStablecoinAccount,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_2022_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             })
             .rpc();
         console.log("===>init config sig:", sig);
@@ -102,7 +234,60 @@ describe("stablecoin", () => {
 
     });
 
+    it("register collateral type......", async () => {
+        const sig = await program.methods
+            .processRegisterCollateralType(
+                timestamp,
+                SOL_PRICE_FEED_ID,
+                new anchor.BN(100000),
+                new anchor.BN(0),//不按slot检查价格延迟
+                new anchor.BN("1000000000000000"),
+            )
+            .accountsPartial({
+                collateralMint: collateralMint,
+                collateralTokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("===>register collateral type sig:", sig);
+
+        const collateralType = await program.account.collateralType.fetch(collateralTypePDA);
+        console.log("--->collateralType:", collateralType);
+        expect(collateralType.collateralMint.toBase58()).to.equal(collateralMint.toBase58());
+        expect(collateralType.maxAge.toNumber()).to.equal(100000);
+        expect(collateralType.debtCeiling.toString()).to.equal("1000000000000000");
+        expect(collateralType.enabled).to.be.true;
+    });
+
+    it("poke price......", async () => {
+        const [priceHistoryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("price_history_account"), collateralTypePDA.toBuffer()],
+            programId
+        );
+        const priceHistoryBefore = await program.account.priceHistory.fetchNullable(priceHistoryPDA);
+
+        const sig = await program.methods
+            .processPokePrice(timestamp)
+            .accountsPartial({
+                collateralType: collateralTypePDA,
+                priceUpdate: solUsdPriceFeedAccountPubkey,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->poke price sig:", sig);
+
+        const priceHistory = await program.account.priceHistory.fetch(priceHistoryPDA);
+        console.log("--->price history count:", priceHistory.count);
+        expect(priceHistory.count).to.equal((priceHistoryBefore?.count ?? 0) + 1);
+    });
+
     it("deposit and mint......", async () => {
+        //把SOL包装成wSOL作为抵押资产
+        await createWrappedNativeAccount(
+            connection,
+            wallet.payer,
+            wallet.publicKey,
+            10000000000,
+        );
+
         console.log("Required accounts:");
         console.log({
             depositor: wallet.publicKey.toBase58(),
@@ -118,12 +303,17 @@ describe("stablecoin", () => {
         });
 
         const sig = await program.methods
-            .processDepositAndMint(timestamp, new anchor.BN(8000000000))
+            .processDepositAndMint(timestamp, new anchor.BN(8000000000), new anchor.BN(50000000000))
             .accountsPartial(
                 {
                     // depositor: wallet.publicKey,
                     // configAccount: configAccountPDA,
                     priceUpdate: solUsdPriceFeedAccountPubkey,
+                    priceHistory: null,//健康因子使用即时价格
+                    priceOverride: null,
+                    collateralMint: collateralMint,
+                    depositorCollateralAccount: depositorCollateralAccount,
+                    collateralTokenProgram: TOKEN_PROGRAM_ID,
                     // mintAccount: mintAccountPDA,
                     // collateralAccount: collateralAccountPDA,
                     // depositedAssetAccount: depositedAssetAccountPAD,
@@ -145,8 +335,8 @@ describe("stablecoin", () => {
         console.log("===>deposit and mint sig:", sig);
 
         // 获取depositedAssetAccount账户余额
-        const balance = await connection.getBalance(depositedAssetAccountPAD);
-        console.log(`depositedAssetAccount Balance: ${balance} lamports`);
+        const balance = await connection.getTokenAccountBalance(depositedAssetAccountPAD);
+        console.log("depositedAssetAccount Balance:", balance.value);
 
         // 验证collateralAccount结果
         const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
@@ -172,11 +362,98 @@ describe("stablecoin", () => {
 
     });
 
+    it("top up collateral......", async () => {
+        await createWrappedNativeAccount(
+            connection,
+            wallet.payer,
+            wallet.publicKey,
+            2000000000,
+        ).catch(() => undefined);
+        const collateralBefore = await program.account.collateral.fetch(collateralAccountPDA);
+        const sig = await program.methods
+            .processDepositCollateral(timestamp, new anchor.BN(1000000000))
+            .accountsPartial({
+                collateralMint: collateralMint,
+                depositorCollateralAccount: depositorCollateralAccount,
+                collateralTokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("===>top up collateral sig:", sig);
+
+        const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
+        console.log("--->collateralAccount after top up:", collateralAccount);
+        expect(collateralAccount.depositedAssetAmount.sub(collateralBefore.depositedAssetAmount).toNumber())
+            .to.equal(1000000000);
+    });
+
+    it("mint against collateral......", async () => {
+        const collateralBefore = await program.account.collateral.fetch(collateralAccountPDA);
+        const sig = await program.methods
+            .processMintAgainstCollateral(timestamp, new anchor.BN(1000000000))
+            .accountsPartial({
+                collateralAccount: collateralAccountPDA,
+                priceUpdate: solUsdPriceFeedAccountPubkey,
+                priceHistory: null,//健康因子使用即时价格
+                priceOverride: null,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("===>mint against collateral sig:", sig);
+
+        const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
+        console.log("--->collateralAccount after mint:", collateralAccount);
+        expect(collateralAccount.normalizedDebt.gt(collateralBefore.normalizedDebt)).to.be.true;
+    });
+
+    it("repay debt......", async () => {
+        const collateralBefore = await program.account.collateral.fetch(collateralAccountPDA);
+        const sig = await program.methods
+            .processRepayDebt(timestamp, new anchor.BN(1000000000))
+            .accountsPartial({
+                collateralAccount: collateralAccountPDA,
+                depositorStablecoinAccount: receiveStablecoinAccount,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("===>repay debt sig:", sig);
+
+        const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
+        console.log("--->collateralAccount after repay:", collateralAccount);
+        expect(collateralAccount.normalizedDebt.lt(collateralBefore.normalizedDebt)).to.be.true;
+    });
+
+    it("withdraw collateral......", async () => {
+        const collateralBefore = await program.account.collateral.fetch(collateralAccountPDA);
+        const sig = await program.methods
+            .processWithdrawCollateral(timestamp, new anchor.BN(500000000))
+            .accountsPartial({
+                collateralMint: collateralMint,
+                depositorCollateralAccount: depositorCollateralAccount,
+                priceUpdate: solUsdPriceFeedAccountPubkey,
+                priceHistory: null,//健康因子使用即时价格
+                priceOverride: null,
+                collateralTokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("===>withdraw collateral sig:", sig);
+
+        const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
+        console.log("--->collateralAccount after withdraw:", collateralAccount);
+        expect(collateralBefore.depositedAssetAmount.sub(collateralAccount.depositedAssetAmount).toNumber())
+            .to.equal(500000000);
+    });
+
     it("burn stablecoin and redeem collateral......", async () => {
         const amount_to_burn = 20888713420;
         const sig = await program.methods
             .processBurnAndRedeem(timestamp, new anchor.BN(amount_to_burn))
-            .accounts({priceUpdate: solUsdPriceFeedAccountPubkey})
+            .accountsPartial({
+                priceUpdate: solUsdPriceFeedAccountPubkey,
+                priceHistory: null,//健康因子使用即时价格
+                priceOverride: null,
+                depositorStablecoinAccount: receiveStablecoinAccount,
+                collateralMint: collateralMint,
+                depositorCollateralAccount: depositorCollateralAccount,
+                collateralTokenProgram: TOKEN_PROGRAM_ID,
+            })
             .rpc({skipPreflight: true, commitment: "confirmed"});
         const latestBlockhash = await connection.getLatestBlockhash();
         await connection.confirmTransaction({
@@ -193,8 +470,8 @@ describe("stablecoin", () => {
         console.log("burn stablecoin and redeem collateral sig:", sig);
 
         //查看depositedAssetBalance在burn和redeem后的数据
-        const depositedAssetBalance = await connection.getBalance(depositedAssetAccountPAD);
-        console.log("--->deposited Asset Balance after burn and redeem:", depositedAssetBalance);
+        const depositedAssetBalance = await connection.getTokenAccountBalance(depositedAssetAccountPAD);
+        console.log("--->deposited Asset Balance after burn and redeem:", depositedAssetBalance.value);
 
         //查看collateralAccount在burn和redeem后的数据
         const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
@@ -219,34 +496,499 @@ describe("stablecoin", () => {
         });
     });
 
-    it("update config......", async () => {
-        const sig = await program.methods.processUpdateConfig(
+    it("queue and execute config change......", async () => {
+        const queueSig = await program.methods.processQueueConfigChange(
             timestamp,
-            new anchor.BN(85),
-            new anchor.BN(15),
-            // new anchor.BN(95),//min_health_factor = 95时不能清算
-            new anchor.BN(100),//min_health_factor = 100时不能清算
-        ).rpc({skipPreflight: true, commitment: "confirmed"});
-        const lastestBlockhash = await connection.getLatestBlockhash();
-        await connection.confirmTransaction({
-            signature: sig,
-            blockhash: lastestBlockhash.blockhash,
-            lastValidBlockHeight: lastestBlockhash.lastValidBlockHeight
-        });
+            {
+                maxLtv: null,
+                liquidationThreshold: new anchor.BN(85),
+                liquidationBonus: new anchor.BN(15),
+                // minHealthFactor: new anchor.BN(95),//min_health_factor = 95时不能清算
+                minHealthFactor: new anchor.BN(100),//min_health_factor = 100时不能清算
+                riskParamTimelockSecs: null,
+            }
+        ).accountsPartial({
+            collateralType: null,
+            pendingConfigChange: marketPendingConfigChangePDA,
+        }).rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->queue config change sig:", queueSig);
+
+        const executeSig = await program.methods
+            .processExecuteConfigChange(timestamp)
+            .accountsPartial({
+                pendingConfigChange: marketPendingConfigChangePDA,
+                proposer: wallet.publicKey,
+                collateralType: null,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->execute config change sig:", executeSig);
+    })
+
+    it("update config......", async () => {
+        //没有修改任何字段时拒绝
+        await expectProgramError(
+            program.methods.processUpdateConfig(timestamp, updateConfigParams({})).rpc(),
+            "InvalidConfigChange",
+        );
+
+        const sig = await program.methods
+            .processUpdateConfig(timestamp, updateConfigParams({redeemFeeBps: new anchor.BN(40)}))
+            .rpc({commitment: "confirmed"});
         console.log("--->updateConfig sig:", sig);
 
         const configAccount = await program.account.config.fetch(configAccountPDA);
-        console.log("--->configAccount after update:", configAccount);
+        expect(configAccount.redeemFeeBps.toNumber()).to.equal(40);
+
+        //ConfigUpdated事件记录修改前后的值
+        const tx = await connection.getTransaction(sig, {commitment: "confirmed", maxSupportedTransactionVersion: 0});
+        const eventParser = new anchor.EventParser(programId, program.coder);
+        const events = [...eventParser.parseLogs(tx.meta.logMessages)]
+            .filter((event) => event.name === "configUpdated");
+        expect(events).to.have.lengthOf(1);
+        expect(events[0].data.field).to.equal("redeem_fee_bps");
+        expect(events[0].data.oldValue.u64["0"].toNumber()).to.equal(30);
+        expect(events[0].data.newValue.u64["0"].toNumber()).to.equal(40);
+    })
+
+    it("multisig update config......", async () => {
+        //创建1-of-1多签，把风险管理员授予多签签名PDA，通过多签把铸币手续费从0.3%改为0.5%
+        const multisigId = new anchor.BN(timestamp);
+        const [multisigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("multisig_account"), wallet.publicKey.toBuffer(), multisigId.toArrayLike(Buffer, "le", 8)],
+            programId
+        );
+        const [multisigSignerPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("multisig_signer_account"), multisigPDA.toBuffer()],
+            programId
+        );
+        const [transactionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("multisig_transaction_account"), multisigPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+            programId
+        );
+
+        const createSig = await program.methods
+            .processCreateMultisig(multisigId, [wallet.publicKey], 1)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->create multisig sig:", createSig);
+
+        const grantSig = await program.methods
+            .processGrantRole(timestamp, {riskAdmin: {}}, multisigSignerPDA)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->grant risk admin to multisig sig:", grantSig);
+
+        const updateConfigIx = await program.methods
+            .processUpdateConfig(timestamp, updateConfigParams({mintFeeBps: new anchor.BN(50)}))
+            .accountsPartial({
+                admin: multisigSignerPDA,
+            })
+            .instruction();
+
+        const proposeSig = await program.methods
+            .processProposeMultisigTransaction(
+                programId,
+                updateConfigIx.keys.map((key) => ({
+                    pubkey: key.pubkey,
+                    isSigner: key.isSigner,
+                    isWritable: key.isWritable,
+                })),
+                updateConfigIx.data,
+            )
+            .accountsPartial({
+                multisig: multisigPDA,
+                transaction: transactionPDA,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->propose multisig transaction sig:", proposeSig);
+
+        //提议人已自动批准，1-of-1多签可以直接执行
+        const executeSig = await program.methods
+            .processExecuteMultisigTransaction()
+            .accountsPartial({
+                multisig: multisigPDA,
+                transaction: transactionPDA,
+            })
+            .remainingAccounts([
+                ...updateConfigIx.keys.map((key) => ({...key, isSigner: false})),
+                {pubkey: programId, isSigner: false, isWritable: false},
+            ])
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->execute multisig transaction sig:", executeSig);
+
+        //多签执行后铸币手续费已修改
+        const configAccount = await program.account.config.fetch(configAccountPDA);
+        expect(configAccount.mintFeeBps.toNumber()).to.equal(50);
+
+        //把风险管理员授予回钱包，后续测试继续使用
+        await program.methods
+            .processGrantRole(timestamp, {riskAdmin: {}}, wallet.publicKey)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+    })
+
+    it("get market totals......", async () => {
+        //remaining_accounts按[抵押资产类型, 价格账户]成对传入
+        const totals = await program.methods
+            .processGetMarketTotals(timestamp)
+            .remainingAccounts([
+                {pubkey: collateralTypePDA, isSigner: false, isWritable: false},
+                {pubkey: new anchor.web3.PublicKey(solUsdPriceFeedAccountPubkey), isSigner: false, isWritable: false},
+            ])
+            .view();
+        console.log("--->market totals:", {
+            totalDebt: totals.totalDebt.toString(),
+            debtCeiling: totals.debtCeiling.toString(),
+            totalCollateralValue: totals.totalCollateralValue.toString(),
+            collateralRatioWad: totals.collateralRatioWad.toString(),
+        });
+        const configAccount = await program.account.config.fetch(configAccountPDA);
+        expect(totals.debtCeiling.toString()).to.equal(configAccount.debtCeiling.toString());
+        expect(totals.totalDebt.gtn(0)).to.be.true;
+        expect(totals.totalCollateralValue.gt(totals.totalDebt)).to.be.true;
+    })
+
+    it("resume......", async () => {
+        const sig = await program.methods
+            .processResume(timestamp)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->resume sig:", sig);
+
+        const configAccount = await program.account.config.fetch(configAccountPDA);
+        console.log("--->circuit breaker paused until:", configAccount.circuitBreakerPausedUntil.toString());
+    })
+
+    it("grant role......", async () => {
+        //初始化时所有角色都属于authority，这里重新授予pauser角色
+        const sig = await program.methods
+            .processGrantRole(timestamp, {pauser: {}}, wallet.publicKey)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->grant role sig:", sig);
+
+        const configAccount = await program.account.config.fetch(configAccountPDA);
+        console.log("--->pauser:", configAccount.roles.pauser.toBase58());
+    })
+
+    it("pause and unpause mint......", async () => {
+        //暂停铸币(PAUSE_MINT = 2)
+        const pauseSig = await program.methods
+            .processSetPauseFlags(timestamp, 2)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->pause mint sig:", pauseSig);
+
+        //暂停期间铸币被拒绝
+        await expectProgramError(
+            program.methods
+                .processMintAgainstCollateral(timestamp, new anchor.BN(1000000))
+                .accountsPartial({
+                    collateralAccount: collateralAccountPDA,
+                    priceUpdate: solUsdPriceFeedAccountPubkey,
+                    priceHistory: null,
+                    priceOverride: null,
+                })
+                .rpc(),
+            "OperationPaused",
+        );
+
+        //还款和追加抵押不受影响
+        const collateralBefore = await program.account.collateral.fetch(collateralAccountPDA);
+        const repaySig = await program.methods
+            .processRepayDebt(timestamp, new anchor.BN(1000000))
+            .accountsPartial({
+                collateralAccount: collateralAccountPDA,
+                depositorStablecoinAccount: receiveStablecoinAccount,
+            })
+            .rpc({commitment: "confirmed"});
+        console.log("--->repay while mint paused sig:", repaySig);
+        const depositSig = await program.methods
+            .processDepositCollateral(timestamp, new anchor.BN(100000000))
+            .accountsPartial({
+                collateralMint: collateralMint,
+                depositorCollateralAccount: depositorCollateralAccount,
+                collateralTokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .rpc({commitment: "confirmed"});
+        console.log("--->deposit while mint paused sig:", depositSig);
+        const collateralAfter = await program.account.collateral.fetch(collateralAccountPDA);
+        expect(collateralAfter.normalizedDebt.lt(collateralBefore.normalizedDebt)).to.be.true;
+
+        const unpauseSig = await program.methods
+            .processSetPauseFlags(timestamp, 0)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->unpause sig:", unpauseSig);
+    })
+
+    it("set price override......", async () => {
+        //经过timelock后才生效，只在预言机价格过期时代替预言机价格
+        const sig = await program.methods
+            .processSetPriceOverride(
+                timestamp,
+                new anchor.BN(15000000000),
+                new anchor.BN(10000000),
+                -8,
+                new anchor.BN(3600),
+            )
+            .accountsPartial({
+                collateralType: collateralTypePDA,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->set price override sig:", sig);
+
+        const [priceOverridePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("price_override_account"), collateralTypePDA.toBuffer()],
+            programId
+        );
+        const priceOverride = await program.account.priceOverride.fetch(priceOverridePDA);
+        console.log("--->price override effective at:", priceOverride.effectiveAt.toString());
+    })
+
+    it("hand over authority and reject old key......", async () => {
+        //把管理权限转给新的密钥(实际使用时可以是多签PDA)，旧管理员持有的所有角色随之转移
+        const newAuthority = anchor.web3.Keypair.generate();
+        await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+            anchor.web3.SystemProgram.transfer({
+                fromPubkey: wallet.publicKey,
+                toPubkey: newAuthority.publicKey,
+                lamports: anchor.web3.LAMPORTS_PER_SOL,
+            })
+        ));
+
+        const proposeSig = await program.methods
+            .processProposeAuthority(timestamp, newAuthority.publicKey)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->propose authority sig:", proposeSig);
+        const acceptSig = await program.methods
+            .processAcceptAuthority(timestamp)
+            .accountsPartial({
+                pendingAuthority: newAuthority.publicKey,
+            })
+            .signers([newAuthority])
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->accept authority sig:", acceptSig);
+
+        let configAccount = await program.account.config.fetch(configAccountPDA);
+        expect(configAccount.authority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+        for (const holder of Object.values(configAccount.roles)) {
+            expect(holder.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+        }
+
+        //旧密钥调用每个需要角色的指令都被拒绝
+        //风险管理员
+        await expectProgramError(
+            program.methods.processUpdateConfig(timestamp, updateConfigParams({stabilityFeeBps: new anchor.BN(200)})).rpc(),
+            "Unauthorized",
+        );
+        const newCollateralMint = await createMint(connection, wallet.payer, wallet.publicKey, null, 9);
+        await expectProgramError(
+            program.methods
+                .processRegisterCollateralType(timestamp, SOL_PRICE_FEED_ID, new anchor.BN(100000), new anchor.BN(0), new anchor.BN(0))
+                .accountsPartial({
+                    collateralMint: newCollateralMint,
+                    collateralTokenProgram: TOKEN_PROGRAM_ID,
+                })
+                .rpc(),
+            "Unauthorized",
+        );
+        const riskParamsChange = {
+            maxLtv: null,
+            liquidationThreshold: null,
+            liquidationBonus: null,
+            minHealthFactor: new anchor.BN(100),
+            riskParamTimelockSecs: null,
+        };
+        await expectProgramError(
+            program.methods
+                .processQueueConfigChange(timestamp, riskParamsChange)
+                .accountsPartial({collateralType: null, pendingConfigChange: marketPendingConfigChangePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        await program.methods
+            .processQueueConfigChange(timestamp, riskParamsChange)
+            .accountsPartial({
+                admin: newAuthority.publicKey,
+                collateralType: null,
+                pendingConfigChange: marketPendingConfigChangePDA,
+            })
+            .signers([newAuthority])
+            .rpc({commitment: "confirmed"});
+        await expectProgramError(
+            program.methods
+                .processExecuteConfigChange(timestamp)
+                .accountsPartial({
+                    pendingConfigChange: marketPendingConfigChangePDA,
+                    proposer: newAuthority.publicKey,
+                    collateralType: null,
+                })
+                .rpc(),
+            "Unauthorized",
+        );
+        await expectProgramError(
+            program.methods
+                .processCancelConfigChange(timestamp)
+                .accountsPartial({pendingConfigChange: marketPendingConfigChangePDA, proposer: newAuthority.publicKey})
+                .rpc(),
+            "Unauthorized",
+        );
+        await program.methods
+            .processCancelConfigChange(timestamp)
+            .accountsPartial({
+                admin: newAuthority.publicKey,
+                pendingConfigChange: marketPendingConfigChangePDA,
+                proposer: newAuthority.publicKey,
+            })
+            .signers([newAuthority])
+            .rpc({commitment: "confirmed"});
+        //预言机管理员
+        await expectProgramError(
+            program.methods.processUpdateConfig(timestamp, updateConfigParams({twapWindowSecs: new anchor.BN(1800)})).rpc(),
+            "Unauthorized",
+        );
+        await expectProgramError(
+            program.methods
+                .processUpdateCollateralType(timestamp, updateCollateralTypeParams({maxAge: new anchor.BN(100000)}))
+                .accountsPartial({collateralType: collateralTypePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        await expectProgramError(
+            program.methods
+                .processSetPriceOverride(timestamp, new anchor.BN(15000000000), new anchor.BN(10000000), -8, new anchor.BN(3600))
+                .accountsPartial({collateralType: collateralTypePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        await expectProgramError(
+            program.methods
+                .processClearPriceOverride(timestamp)
+                .accountsPartial({collateralType: collateralTypePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        //暂停管理员
+        await expectProgramError(program.methods.processResume(timestamp).rpc(), "Unauthorized");
+        await expectProgramError(program.methods.processSetPauseFlags(timestamp, 2).rpc(), "Unauthorized");
+        await expectProgramError(
+            program.methods
+                .processUpdateCollateralType(timestamp, updateCollateralTypeParams({enabled: false}))
+                .accountsPartial({collateralType: collateralTypePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        //国库管理员
+        await expectProgramError(
+            program.methods
+                .processWithdrawTreasury(timestamp, new anchor.BN(1))
+                .accountsPartial({
+                    recipient: wallet.publicKey,
+                    tokenMint: null,
+                    treasuryTokenAccount: null,
+                    destinationTokenAccount: null,
+                    tokenProgram: null,
+                })
+                .rpc(),
+            "Unauthorized",
+        );
+        //债务上限管理员
+        await expectProgramError(
+            program.methods.processUpdateConfig(timestamp, updateConfigParams({debtCeiling: new anchor.BN("10000000000000000")})).rpc(),
+            "Unauthorized",
+        );
+        await expectProgramError(
+            program.methods
+                .processUpdateCollateralType(timestamp, updateCollateralTypeParams({debtCeiling: new anchor.BN("1000000000000000")}))
+                .accountsPartial({collateralType: collateralTypePDA})
+                .rpc(),
+            "Unauthorized",
+        );
+        //管理员本身
+        await expectProgramError(
+            program.methods.processGrantRole(timestamp, {pauser: {}}, wallet.publicKey).rpc(),
+            "ConstraintHasOne",
+        );
+        await expectProgramError(
+            program.methods.processProposeAuthority(timestamp, wallet.publicKey).rpc(),
+            "ConstraintHasOne",
+        );
+
+        //交还管理权限，后续测试继续使用钱包
+        await program.methods
+            .processProposeAuthority(timestamp, wallet.publicKey)
+            .accountsPartial({authority: newAuthority.publicKey})
+            .signers([newAuthority])
+            .rpc({commitment: "confirmed"});
+        await program.methods
+            .processAcceptAuthority(timestamp)
+            .accountsPartial({pendingAuthority: wallet.publicKey})
+            .rpc({commitment: "confirmed"});
+        configAccount = await program.account.config.fetch(configAccountPDA);
+        for (const holder of Object.values(configAccount.roles)) {
+            expect(holder.toBase58()).to.equal(wallet.publicKey.toBase58());
+        }
+    })
+
+    it("clear price override......", async () => {
+        const sig = await program.methods
+            .processClearPriceOverride(timestamp)
+            .accountsPartial({
+                collateralType: collateralTypePDA,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->clear price override sig:", sig);
+    })
+
+    it("accrue stability fee......", async () => {
+        const configBefore = await program.account.config.fetch(configAccountPDA);
+        const sig = await program.methods
+            .processAccrue(timestamp)
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->accrue sig:", sig);
+
+        const configAccount = await program.account.config.fetch(configAccountPDA);
+        console.log("--->debt index after accrue:", configAccount.debtIndex.toString());
+        expect(configAccount.debtIndex.gt(configBefore.debtIndex)).to.be.true;
+    })
+
+    it("queue and execute collateral type change......", async () => {
+        //抵押资产类型的max_ltv、清算阈值和清算奖励同样需要排队，测试中timelock为0可以立即执行
+        const queueSig = await program.methods.processQueueConfigChange(
+            timestamp,
+            {
+                maxLtv: null,
+                liquidationThreshold: new anchor.BN(85),
+                liquidationBonus: new anchor.BN(15),
+                minHealthFactor: null,
+                riskParamTimelockSecs: null,
+            }
+        ).accountsPartial({
+            collateralType: collateralTypePDA,
+            pendingConfigChange: collateralPendingConfigChangePDA,
+        }).rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->queue collateral type change sig:", queueSig);
+
+        const executeSig = await program.methods
+            .processExecuteConfigChange(timestamp)
+            .accountsPartial({
+                pendingConfigChange: collateralPendingConfigChangePDA,
+                proposer: wallet.publicKey,
+                collateralType: collateralTypePDA,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->execute collateral type change sig:", executeSig);
+
+        const collateralType = await program.account.collateralType.fetch(collateralTypePDA);
+        console.log("--->collateralType after update:", collateralType);
     })
 
     it("liquidate......", async () => {
         const sig = await program.methods
-            .processLiquidate(timestamp, new anchor.BN(20000000000))
+            .processLiquidate(timestamp, new anchor.BN(10000000000))
             .accountsPartial(
                 {
                     // liquidator: wallet.publicKey,
                     collateralAccount: collateralAccountPDA,
                     priceUpdate: solUsdPriceFeedAccountPubkey,
+                    priceHistory: null,//健康因子使用即时价格
+                    priceOverride: null,
+                    collateralMint: collateralMint,
+                    collateralTokenProgram: TOKEN_PROGRAM_ID,
                     // depositedAssetAccount: depositedAssetAccountPAD,
                     // liquidatorStablecoinAccount: receiveStablecoinAccount,
                     // mintAccount: mintAccountPDA,
@@ -262,8 +1004,8 @@ describe("stablecoin", () => {
         console.log("--->liquidate sig:", sig);
 
         //查看depositedAssetBalance在liquidate后的数据
-        const depositedAssetBalance = await connection.getBalance(depositedAssetAccountPAD);
-        console.log("--->deposited Asset Balance after burn and redeem:", depositedAssetBalance);
+        const depositedAssetBalance = await connection.getTokenAccountBalance(depositedAssetAccountPAD);
+        console.log("--->deposited Asset Balance after burn and redeem:", depositedAssetBalance.value);
 
         //查看collateralAccount在liquidate后的数据
         const collateralAccount = await program.account.collateral.fetch(collateralAccountPDA);
@@ -287,4 +1029,32 @@ describe("stablecoin", () => {
             supply: mintInfo.supply.toString(),
         });
     });
+
+    it("withdraw treasury......", async () => {
+        const treasuryBalance = await connection.getTokenAccountBalance(treasuryStablecoinAccount);
+        console.log("--->treasury stablecoin balance:", treasuryBalance.value);
+        const stableCoinBalanceBefore = await connection.getTokenAccountBalance(receiveStablecoinAccount);
+
+        const sig = await program.methods
+            .processWithdrawTreasury(timestamp, new anchor.BN(treasuryBalance.value.amount))
+            .accountsPartial({
+                recipient: null,
+                tokenMint: mintAccountPDA,
+                treasuryTokenAccount: treasuryStablecoinAccount,
+                destinationTokenAccount: receiveStablecoinAccount,
+                tokenProgram: TOKEN_2022_PROGRAM_ID,
+            })
+            .rpc({skipPreflight: true, commitment: "confirmed"});
+        console.log("--->withdraw treasury sig:", sig);
+
+        const stableCoinBalance = await connection.getTokenAccountBalance(receiveStablecoinAccount);
+        console.log("--->stableCoin balance after withdraw treasury:", stableCoinBalance.value);
+        const treasuryBalanceAfter = await connection.getTokenAccountBalance(treasuryStablecoinAccount);
+        expect(treasuryBalanceAfter.value.amount).to.equal("0");
+        expect(
+            new anchor.BN(stableCoinBalance.value.amount)
+                .sub(new anchor.BN(stableCoinBalanceBefore.value.amount))
+                .toString()
+        ).to.equal(treasuryBalance.value.amount);
+    });
 });